@@ -1,49 +1,154 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::env;
 use uuid::Uuid;
 
+/// One signing/verification key in the keyset, identified by a `kid` that gets written into
+/// (and read back from) the JWT header. `secret` holds the raw HMAC secret for `HS*`
+/// algorithms, or PEM-encoded key material for `RS*`/`EdDSA`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JwtKey {
+    pub kid: String,
+    #[serde(default = "default_key_algorithm")]
+    pub algorithm: Algorithm,
+    pub secret: String,
+}
+
+fn default_key_algorithm() -> Algorithm {
+    Algorithm::HS256
+}
+
+impl JwtKey {
+    fn encoding_key(&self) -> Result<EncodingKey, jsonwebtoken::errors::Error> {
+        match self.algorithm {
+            Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 => {
+                EncodingKey::from_rsa_pem(self.secret.as_bytes())
+            }
+            Algorithm::EdDSA => EncodingKey::from_ed_pem(self.secret.as_bytes()),
+            _ => Ok(EncodingKey::from_secret(self.secret.as_bytes())),
+        }
+    }
+
+    fn decoding_key(&self) -> Result<DecodingKey, jsonwebtoken::errors::Error> {
+        match self.algorithm {
+            Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 => {
+                DecodingKey::from_rsa_pem(self.secret.as_bytes())
+            }
+            Algorithm::EdDSA => DecodingKey::from_ed_pem(self.secret.as_bytes()),
+            _ => Ok(DecodingKey::from_secret(self.secret.as_bytes())),
+        }
+    }
+}
+
+/// The `kid` used for the implicit key built from `JWT_SECRET` when no `JWT_KEYS` keyset is
+/// configured, kept stable so tokens minted before a keyset is introduced still verify.
+const DEFAULT_KID: &str = "default";
+
 #[derive(Debug, Clone)]
 pub struct JwtConfig {
-    pub secret: String,
-    pub algorithm: Algorithm,
+    /// The key every new token is signed with and stamped with via its `kid`.
+    pub primary: JwtKey,
+    /// Keys that are no longer used for signing but are still accepted for verification,
+    /// so a rotated-out secret has a migration window instead of invalidating every live
+    /// token instantly.
+    pub retired: Vec<JwtKey>,
     pub expiration_hours: i64,
+    /// Overrides `expiration_hours` with minute-granularity when set, so access tokens can
+    /// be kept genuinely short-lived (e.g. 15 minutes) now that `/auth/refresh` gives clients
+    /// a way to mint a new one without re-authenticating.
+    pub expiration_minutes: Option<i64>,
+    pub refresh_expiration_days: i64,
 }
 
 impl JwtConfig {
     pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
-        let secret = env::var("JWT_SECRET")
-            .unwrap_or_else(|_| {
-                tracing::warn!("JWT_SECRET not set, using default (not secure for production)");
-                "your-secret-key-change-this-in-production".to_string()
-            });
-
         let expiration_hours = env::var("JWT_EXPIRATION_HOURS")
             .unwrap_or_else(|_| "24".to_string())
             .parse()
             .unwrap_or(24);
 
+        let expiration_minutes = env::var("JWT_EXPIRATION_MINUTES")
+            .ok()
+            .and_then(|value| value.parse().ok());
+
+        let refresh_expiration_days = env::var("JWT_REFRESH_EXPIRATION_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+
+        let (primary, retired) = match env::var("JWT_KEYS") {
+            Ok(raw) => {
+                let mut keys: Vec<JwtKey> = raw
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|entry| !entry.is_empty())
+                    .map(|entry| {
+                        let (kid, secret) = entry
+                            .split_once(':')
+                            .ok_or_else(|| format!("invalid JWT_KEYS entry {entry:?}, expected kid:secret"))?;
+                        Ok(JwtKey {
+                            kid: kid.to_string(),
+                            algorithm: Algorithm::HS256,
+                            secret: secret.to_string(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                if keys.is_empty() {
+                    return Err("JWT_KEYS must contain at least one kid:secret pair".into());
+                }
+
+                // JWT_ACTIVE_KID picks which key in the set signs new tokens; the rest stay
+                // around only to verify tokens minted before the active key last rotated.
+                let primary_index = match env::var("JWT_ACTIVE_KID") {
+                    Ok(active_kid) => keys
+                        .iter()
+                        .position(|key| key.kid == active_kid)
+                        .ok_or_else(|| format!("JWT_ACTIVE_KID {active_kid:?} not present in JWT_KEYS"))?,
+                    Err(_) => 0,
+                };
+                let primary = keys.remove(primary_index);
+                (primary, keys)
+            }
+            Err(_) => {
+                let secret = env::var("JWT_SECRET").unwrap_or_else(|_| {
+                    tracing::warn!("JWT_SECRET not set, using default (not secure for production)");
+                    "your-secret-key-change-this-in-production".to_string()
+                });
+
+                (
+                    JwtKey {
+                        kid: DEFAULT_KID.to_string(),
+                        algorithm: Algorithm::HS256,
+                        secret,
+                    },
+                    Vec::new(),
+                )
+            }
+        };
+
         Ok(Self {
-            secret,
-            algorithm: Algorithm::HS256,
+            primary,
+            retired,
             expiration_hours,
+            expiration_minutes,
+            refresh_expiration_days,
         })
     }
 
-    pub fn encoding_key(&self) -> EncodingKey {
-        EncodingKey::from_secret(self.secret.as_ref())
-    }
-
-    pub fn decoding_key(&self) -> DecodingKey {
-        DecodingKey::from_secret(self.secret.as_ref())
-    }
-
-    pub fn validation(&self) -> Validation {
-        let mut validation = Validation::new(self.algorithm);
-        validation.validate_exp = true;
-        validation.validate_nbf = false;
-        validation
+    /// Look up a key by `kid`, falling back to the primary key when the token predates key
+    /// rotation and carries no `kid` at all.
+    fn key_for_kid(&self, kid: Option<&str>) -> Option<&JwtKey> {
+        match kid {
+            Some(kid) => std::iter::once(&self.primary)
+                .chain(self.retired.iter())
+                .find(|key| key.kid == kid),
+            None => Some(&self.primary),
+        }
     }
 }
 
@@ -56,10 +161,19 @@ pub struct Claims {
     pub iat: i64,       // Issued at
 }
 
+/// How long a freshly minted access token stays valid: `expiration_minutes` takes precedence
+/// over the coarser `expiration_hours` when set.
+pub fn access_token_lifetime(config: &JwtConfig) -> Duration {
+    config
+        .expiration_minutes
+        .map(Duration::minutes)
+        .unwrap_or_else(|| Duration::hours(config.expiration_hours))
+}
+
 impl Claims {
     pub fn new(user_id: Uuid, email: String, username: String, config: &JwtConfig) -> Self {
         let now = Utc::now();
-        let exp = now + Duration::hours(config.expiration_hours);
+        let exp = now + access_token_lifetime(config);
 
         Self {
             sub: user_id.to_string(),
@@ -77,24 +191,116 @@ impl Claims {
 
 pub fn create_token(user_id: Uuid, email: String, username: String, config: &JwtConfig) -> Result<String, jsonwebtoken::errors::Error> {
     let claims = Claims::new(user_id, email, username, config);
-    let header = Header::new(config.algorithm);
-    encode(&header, &claims, &config.encoding_key())
+    let mut header = Header::new(config.primary.algorithm);
+    header.kid = Some(config.primary.kid.clone());
+    encode(&header, &claims, &config.primary.encoding_key()?)
 }
 
+/// Verify a token against whichever key its header's `kid` names (primary or still-trusted
+/// retired key), so a rotated-out signing key keeps validating tokens it already issued.
 pub fn verify_token(token: &str, config: &JwtConfig) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let token_data = decode::<Claims>(token, &config.decoding_key(), &config.validation())?;
+    let header = decode_header(token)?;
+    let key = config
+        .key_for_kid(header.kid.as_deref())
+        .ok_or(jsonwebtoken::errors::ErrorKind::InvalidKeyFormat)?;
+
+    let mut validation = Validation::new(key.algorithm);
+    validation.validate_exp = true;
+    validation.validate_nbf = false;
+
+    let token_data = decode::<Claims>(token, &key.decoding_key()?, &validation)?;
     Ok(token_data.claims)
 }
 
+/// Peek at a token's `kid` and algorithm without verifying its signature, for attaching
+/// non-sensitive identifiers to auth tracing. Never decode or log the token itself — only
+/// this header metadata is safe to emit.
+pub fn peek_header(token: &str) -> (String, String) {
+    match decode_header(token) {
+        Ok(header) => (
+            header.kid.unwrap_or_else(|| "none".to_string()),
+            format!("{:?}", header.alg),
+        ),
+        Err(_) => ("none".to_string(), "unknown".to_string()),
+    }
+}
+
+/// A freshly-minted opaque refresh token, ready to be persisted and handed to the client.
+///
+/// `token` is the plaintext value delivered to the client (as an HttpOnly cookie); only
+/// `token_hash` is ever stored server-side, so a leaked database never exposes usable tokens.
+#[derive(Debug, Clone)]
+pub struct IssuedRefreshToken {
+    pub token: String,
+    pub token_hash: String,
+    pub family_id: Uuid,
+    pub expires_at: chrono::DateTime<Utc>,
+}
+
+/// An access + refresh token pair returned from login, refresh, and similar flows.
+#[derive(Debug, Clone)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: IssuedRefreshToken,
+}
+
+/// Generate a new opaque refresh token (32 random bytes, base64url-encoded) belonging to
+/// `family_id`. Pass the same `family_id` across a rotation chain so that reuse of a revoked
+/// token can invalidate the whole chain at once; pass a new random UUID to start a fresh chain.
+pub fn generate_refresh_token(family_id: Uuid, config: &JwtConfig) -> IssuedRefreshToken {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let token = URL_SAFE_NO_PAD.encode(bytes);
+    let token_hash = hash_refresh_token(&token);
+    let expires_at = Utc::now() + Duration::days(config.refresh_expiration_days);
+
+    IssuedRefreshToken {
+        token,
+        token_hash,
+        family_id,
+        expires_at,
+    }
+}
+
+/// Hash a presented refresh token for lookup/comparison against the stored `token_hash`.
+pub fn hash_refresh_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Issue a fresh access+refresh pair. `family_id` should be carried over from the refresh
+/// token being rotated (or created fresh on login) so reuse detection can revoke the family.
+pub fn issue_token_pair(
+    user_id: Uuid,
+    email: String,
+    username: String,
+    family_id: Uuid,
+    config: &JwtConfig,
+) -> Result<TokenPair, jsonwebtoken::errors::Error> {
+    let access_token = create_token(user_id, email, username, config)?;
+    let refresh_token = generate_refresh_token(family_id, config);
+    Ok(TokenPair {
+        access_token,
+        refresh_token,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     fn test_config() -> JwtConfig {
         JwtConfig {
-            secret: "test-secret".to_string(),
-            algorithm: Algorithm::HS256,
+            primary: JwtKey {
+                kid: "test".to_string(),
+                algorithm: Algorithm::HS256,
+                secret: "test-secret".to_string(),
+            },
+            retired: Vec::new(),
             expiration_hours: 1,
+            expiration_minutes: None,
+            refresh_expiration_days: 30,
         }
     }
 
@@ -124,11 +330,88 @@ mod tests {
     fn test_wrong_secret() {
         let config1 = test_config();
         let mut config2 = test_config();
-        config2.secret = "different-secret".to_string();
+        config2.primary.secret = "different-secret".to_string();
 
         let user_id = Uuid::new_v4();
         let token = create_token(user_id, "test@example.com".to_string(), "testuser".to_string(), &config1).unwrap();
         let result = verify_token(&token, &config2);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_generate_refresh_token_is_unique_and_hashable() {
+        let config = test_config();
+        let family_id = Uuid::new_v4();
+
+        let first = generate_refresh_token(family_id, &config);
+        let second = generate_refresh_token(family_id, &config);
+
+        assert_ne!(first.token, second.token);
+        assert_eq!(first.family_id, family_id);
+        assert_eq!(hash_refresh_token(&first.token), first.token_hash);
+        assert_ne!(first.token_hash, second.token_hash);
+    }
+
+    #[test]
+    fn test_issue_token_pair_shares_family_id() {
+        let config = test_config();
+        let family_id = Uuid::new_v4();
+        let pair = issue_token_pair(
+            Uuid::new_v4(),
+            "test@example.com".to_string(),
+            "testuser".to_string(),
+            family_id,
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(pair.refresh_token.family_id, family_id);
+        assert!(verify_token(&pair.access_token, &config).is_ok());
+    }
+
+    #[test]
+    fn test_token_carries_primary_kid() {
+        let config = test_config();
+        let token = create_token(Uuid::new_v4(), "test@example.com".to_string(), "testuser".to_string(), &config).unwrap();
+        let header = decode_header(&token).unwrap();
+        assert_eq!(header.kid, Some(config.primary.kid.clone()));
+    }
+
+    #[test]
+    fn test_retired_key_still_verifies_after_rotation() {
+        let old_key = JwtKey {
+            kid: "old".to_string(),
+            algorithm: Algorithm::HS256,
+            secret: "old-secret".to_string(),
+        };
+        let old_config = JwtConfig {
+            primary: old_key.clone(),
+            retired: Vec::new(),
+            expiration_hours: 1,
+            expiration_minutes: None,
+            refresh_expiration_days: 30,
+        };
+
+        let token = create_token(Uuid::new_v4(), "test@example.com".to_string(), "testuser".to_string(), &old_config).unwrap();
+
+        // Rotate: a new primary key takes over signing, but the old key is kept around to
+        // verify tokens minted before the rotation.
+        let rotated_config = JwtConfig {
+            primary: JwtKey {
+                kid: "new".to_string(),
+                algorithm: Algorithm::HS256,
+                secret: "new-secret".to_string(),
+            },
+            retired: vec![old_key],
+            expiration_hours: 1,
+            expiration_minutes: None,
+            refresh_expiration_days: 30,
+        };
+
+        assert!(verify_token(&token, &rotated_config).is_ok());
+
+        let new_token = create_token(Uuid::new_v4(), "test@example.com".to_string(), "testuser".to_string(), &rotated_config).unwrap();
+        let header = decode_header(&new_token).unwrap();
+        assert_eq!(header.kid, Some("new".to_string()));
+    }
 }
\ No newline at end of file
@@ -1,14 +1,54 @@
 use axum::{
     async_trait,
     extract::FromRequestParts,
-    http::{request::Parts, StatusCode},
+    http::{header, request::Parts, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde_json::json;
+use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::auth::jwt::{verify_token, Claims};
+use crate::auth::jwt::{peek_header, verify_token, Claims};
+use crate::error::AppError;
+use crate::repositories::user::UserRepository;
+use crate::state::{AuthMetrics, BlockedUserCache};
+
+/// Cookie carrying the access token for browser clients that can't (or don't want to) set
+/// an `Authorization` header. Mirrors `REFRESH_COOKIE_NAME` in `handlers::auth`.
+pub const ACCESS_TOKEN_COOKIE_NAME: &str = "access_token";
+
+/// Pull the bearer token out of the request: prefer the `Authorization` header, falling back
+/// to the `access_token` cookie only when no `Authorization` header was sent at all, so a
+/// present-but-malformed header is still reported as invalid rather than silently ignored.
+fn extract_token(parts: &Parts) -> Result<String, AuthError> {
+    match parts.headers.get("Authorization") {
+        Some(auth_header) => {
+            let auth_str = auth_header.to_str().map_err(|_| AuthError::InvalidToken)?;
+
+            if !auth_str.starts_with("Bearer ") {
+                return Err(AuthError::InvalidToken);
+            }
+
+            let token = &auth_str[7..];
+            if token.is_empty() {
+                return Err(AuthError::InvalidToken);
+            }
+
+            Ok(token.to_string())
+        }
+        None => extract_access_token_cookie(parts).ok_or(AuthError::MissingToken),
+    }
+}
+
+fn extract_access_token_cookie(parts: &Parts) -> Option<String> {
+    let cookie_header = parts.headers.get(header::COOKIE)?.to_str().ok()?;
+    cookie_header.split(';').find_map(|pair| {
+        let (name, value) = pair.trim().split_once('=')?;
+        (name == ACCESS_TOKEN_COOKIE_NAME).then(|| value.to_string())
+    })
+}
 
 #[derive(Debug, Clone)]
 pub struct AuthUser {
@@ -33,6 +73,7 @@ pub enum AuthError {
     MissingToken,
     InvalidToken,
     ExpiredToken,
+    Blocked,
     InternalError,
 }
 
@@ -42,6 +83,7 @@ impl IntoResponse for AuthError {
             AuthError::MissingToken => (StatusCode::UNAUTHORIZED, "Missing authentication token"),
             AuthError::InvalidToken => (StatusCode::UNAUTHORIZED, "Invalid authentication token"),
             AuthError::ExpiredToken => (StatusCode::UNAUTHORIZED, "Authentication token expired"),
+            AuthError::Blocked => (StatusCode::FORBIDDEN, "Account is blocked"),
             AuthError::InternalError => (StatusCode::INTERNAL_SERVER_ERROR, "Internal authentication error"),
         };
 
@@ -54,52 +96,171 @@ impl IntoResponse for AuthError {
     }
 }
 
-pub struct RequireAuth;
+/// Reject the request if `user_id` is blocked, consulting `BlockedUserCache` before falling
+/// back to a database lookup so a blocked check doesn't cost a query on every request.
+async fn ensure_not_blocked<S>(user_id: Uuid, state: &S) -> Result<(), AuthError>
+where
+    S: Send + Sync + AsRef<PgPool> + AsRef<BlockedUserCache> + AsRef<AuthMetrics>,
+{
+    let cache: &BlockedUserCache = state.as_ref();
+    let blocked = match cache.get(user_id) {
+        Some(blocked) => blocked,
+        None => {
+            let pool: &PgPool = state.as_ref();
+            let blocked = UserRepository::new(pool.clone())
+                .is_blocked(user_id)
+                .await
+                .map_err(|_| AuthError::InternalError)?;
+            cache.set(user_id, blocked);
+            blocked
+        }
+    };
+
+    if blocked {
+        let metrics: &AuthMetrics = state.as_ref();
+        metrics.record_blocked();
+        Err(AuthError::Blocked)
+    } else {
+        Ok(())
+    }
+}
+
+/// Verify `token`'s signature and blocked status inside a single `auth.verify` span (`kid`,
+/// `algorithm`), whose outcome events carry `user_id` (once known) and `outcome`, and update
+/// `AuthMetrics` — the one choke point every extractor routes through, so no call site is
+/// tempted to log the token or key material directly.
+async fn authenticate<S>(token: &str, state: &S) -> Result<Claims, AuthError>
+where
+    S: Send
+        + Sync
+        + AsRef<crate::auth::JwtConfig>
+        + AsRef<PgPool>
+        + AsRef<BlockedUserCache>
+        + AsRef<AuthMetrics>,
+{
+    let (kid, algorithm) = peek_header(token);
+    let span = tracing::info_span!("auth.verify", kid = %kid, algorithm = %algorithm);
+    let _enter = span.enter();
+
+    let jwt_config: &crate::auth::JwtConfig = state.as_ref();
+    let metrics: &AuthMetrics = state.as_ref();
+
+    let claims = verify_token(token, jwt_config).map_err(|e| {
+        let (outcome, rejection) = match e.kind() {
+            jsonwebtoken::errors::ErrorKind::ExpiredSignature => {
+                metrics.record_expired();
+                ("expired", AuthError::ExpiredToken)
+            }
+            _ => {
+                metrics.record_malformed();
+                ("malformed", AuthError::InvalidToken)
+            }
+        };
+        tracing::warn!(kid = %kid, algorithm = %algorithm, outcome, "JWT verification failed");
+        rejection
+    })?;
+
+    let user_id = claims.user_id().map_err(|_| {
+        metrics.record_malformed();
+        tracing::warn!(
+            kid = %kid,
+            algorithm = %algorithm,
+            outcome = "malformed",
+            "JWT carried an unparseable subject claim"
+        );
+        AuthError::InvalidToken
+    })?;
+
+    if let Err(rejection) = ensure_not_blocked(user_id, state).await {
+        let outcome = if matches!(rejection, AuthError::Blocked) {
+            "blocked"
+        } else {
+            "error"
+        };
+        tracing::warn!(
+            user_id = %user_id,
+            kid = %kid,
+            algorithm = %algorithm,
+            outcome,
+            "auth rejected after signature verification"
+        );
+        return Err(rejection);
+    }
+
+    metrics.record_verified();
+    tracing::info!(
+        user_id = %user_id,
+        kid = %kid,
+        algorithm = %algorithm,
+        outcome = "verified",
+        "auth verified"
+    );
+
+    Ok(claims)
+}
+
+/// Credentials pulled from an `Authorization: Basic` header, still in need of lookup and
+/// Argon2 verification against the stored hash — this extractor only handles the HTTP
+/// framing, not authentication itself. Used by the Basic-auth login handler as an
+/// alternative to posting a JSON body.
+#[derive(Debug, Clone)]
+pub struct BasicCredentials {
+    pub email: String,
+    pub password: String,
+}
 
 #[async_trait]
-impl<S> FromRequestParts<S> for RequireAuth
+impl<S> FromRequestParts<S> for BasicCredentials
 where
-    S: Send + Sync + AsRef<crate::auth::JwtConfig>,
+    S: Send + Sync,
 {
     type Rejection = AuthError;
 
-    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        // Extract the Authorization header
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
         let auth_header = parts
             .headers
-            .get("Authorization")
-            .ok_or(AuthError::MissingToken)?;
-
-        let auth_str = auth_header
+            .get(header::AUTHORIZATION)
+            .ok_or(AuthError::MissingToken)?
             .to_str()
             .map_err(|_| AuthError::InvalidToken)?;
 
-        // Check for Bearer token format
-        if !auth_str.starts_with("Bearer ") {
-            return Err(AuthError::InvalidToken);
-        }
+        let encoded = auth_header
+            .strip_prefix("Basic ")
+            .ok_or(AuthError::InvalidToken)?;
 
-        let token = &auth_str[7..]; // Remove "Bearer " prefix
+        let decoded = STANDARD
+            .decode(encoded)
+            .map_err(|_| AuthError::InvalidToken)?;
+        let decoded = String::from_utf8(decoded).map_err(|_| AuthError::InvalidToken)?;
 
-        if token.is_empty() {
-            return Err(AuthError::InvalidToken);
-        }
+        let (email, password) = decoded
+            .split_once(':')
+            .ok_or(AuthError::InvalidToken)?;
 
-        // Use cached JWT config from app state instead of loading from env every time
-        let jwt_config = state.as_ref();
-        
-        tracing::debug!("Validating JWT token with secret: {}", &jwt_config.secret[..10]);
-        tracing::debug!("Token to validate: {}", &token[..token.len().min(20)]);
-        
-        verify_token(token, jwt_config)
-            .map_err(|e| {
-                tracing::error!("JWT validation failed: {:?}", e);
-                match e.kind() {
-                    jsonwebtoken::errors::ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
-                    _ => AuthError::InvalidToken,
-                }
-            })?;
+        Ok(BasicCredentials {
+            email: email.to_string(),
+            password: password.to_string(),
+        })
+    }
+}
+
+pub struct RequireAuth;
+
+#[async_trait]
+impl<S> FromRequestParts<S> for RequireAuth
+where
+    S: Send
+        + Sync
+        + AsRef<crate::auth::JwtConfig>
+        + AsRef<PgPool>
+        + AsRef<BlockedUserCache>
+        + AsRef<AuthMetrics>,
+{
+    type Rejection = AuthError;
 
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let token = extract_token(parts)?;
+        authenticate(&token, state).await?;
         Ok(RequireAuth)
     }
 }
@@ -107,53 +268,167 @@ where
 #[async_trait]
 impl<S> FromRequestParts<S> for AuthUser
 where
-    S: Send + Sync + AsRef<crate::auth::JwtConfig>,
+    S: Send
+        + Sync
+        + AsRef<crate::auth::JwtConfig>
+        + AsRef<PgPool>
+        + AsRef<BlockedUserCache>
+        + AsRef<AuthMetrics>,
 {
     type Rejection = AuthError;
 
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        // First ensure we have a valid auth token
-        let _auth = RequireAuth::from_request_parts(parts, state).await?;
+        let token = extract_token(parts)?;
+        let claims = authenticate(&token, state).await?;
+        AuthUser::from_claims(claims)
+    }
+}
 
-        // Extract the Authorization header again
-        let auth_header = parts
-            .headers
-            .get("Authorization")
-            .ok_or(AuthError::MissingToken)?;
+/// Like [`AuthUser`], but additionally requires the caller's `is_staff` flag, for admin-only
+/// operations (listing every account, blocking or unblocking an account, purging expired
+/// soft-deleted accounts). Rejects with `AppError::Auth`
+/// rather than a distinct "forbidden" case, so a non-staff caller learns no more from the
+/// response than an unauthenticated one would.
+#[derive(Debug, Clone)]
+pub struct StaffUser(pub AuthUser);
 
-        let auth_str = auth_header
-            .to_str()
-            .map_err(|_| AuthError::InvalidToken)?;
+#[async_trait]
+impl<S> FromRequestParts<S> for StaffUser
+where
+    S: Send
+        + Sync
+        + AsRef<crate::auth::JwtConfig>
+        + AsRef<PgPool>
+        + AsRef<BlockedUserCache>
+        + AsRef<AuthMetrics>,
+{
+    type Rejection = AppError;
 
-        let token = &auth_str[7..]; // Remove "Bearer " prefix
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state)
+            .await
+            .map_err(|_| AppError::Auth("Authentication required".to_string()))?;
+
+        let pool: &PgPool = state.as_ref();
+        let is_staff = UserRepository::new(pool.clone()).is_staff(user.id).await?;
+        if !is_staff {
+            return Err(AppError::Auth("Staff access required".to_string()));
+        }
 
-        // Use cached JWT config from app state
-        let jwt_config = state.as_ref();
-        
-        tracing::debug!("Extracting user from JWT token");
+        Ok(StaffUser(user))
+    }
+}
 
-        // Verify the JWT token
-        let claims = verify_token(token, jwt_config)
-            .map_err(|e| match e.kind() {
-                jsonwebtoken::errors::ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
-                _ => AuthError::InvalidToken,
-            })?;
+/// Like [`AuthUser`], but additionally requires the caller's `verified` flag, for routes that
+/// need proof of email ownership rather than just a live session (e.g. posting content, not
+/// just reading your own `/users/me`). An unverified account still authenticates fine via
+/// plain `AuthUser`; this extractor is only for the subset of routes that should gate on it.
+#[derive(Debug, Clone)]
+pub struct VerifiedUser(pub AuthUser);
 
-        // Convert claims to AuthUser
-        AuthUser::from_claims(claims)
+#[async_trait]
+impl<S> FromRequestParts<S> for VerifiedUser
+where
+    S: Send
+        + Sync
+        + AsRef<crate::auth::JwtConfig>
+        + AsRef<PgPool>
+        + AsRef<BlockedUserCache>
+        + AsRef<AuthMetrics>,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state)
+            .await
+            .map_err(|_| AppError::Auth("Authentication required".to_string()))?;
+
+        let pool: &PgPool = state.as_ref();
+        let verified = UserRepository::new(pool.clone()).is_verified(user.id).await?;
+        if !verified {
+            return Err(AppError::Auth("Email verification required".to_string()));
+        }
+
+        Ok(VerifiedUser(user))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Claims
+where
+    S: Send + Sync + AsRef<crate::auth::JwtConfig> + AsRef<PgPool>,
+{
+    type Rejection = AppError;
+
+    /// Extract and validate the bearer token, then confirm the subject still exists —
+    /// catching the window between a user's row being purged (see
+    /// `UserRepository::soft_delete_user`/purge) and their outstanding tokens expiring on
+    /// their own. Handlers that just need the authenticated user id (without the blocked-cache
+    /// check or auth tracing `AuthUser` layers on) can take `claims: Claims` directly.
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let token = extract_token(parts)
+            .map_err(|_| AppError::Auth("Missing or invalid authentication token".to_string()))?;
+
+        let jwt_config: &crate::auth::JwtConfig = state.as_ref();
+        let claims = verify_token(&token, jwt_config)
+            .map_err(|_| AppError::Auth("Invalid or expired authentication token".to_string()))?;
+
+        let user_id = claims
+            .user_id()
+            .map_err(|_| AppError::Auth("Invalid authentication token".to_string()))?;
+
+        let pool: &PgPool = state.as_ref();
+        UserRepository::new(pool.clone())
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::Auth("Invalid authentication token".to_string()))?;
+
+        Ok(claims)
     }
 }
 
+/// Like [`AuthUser`], but resolves to `None` instead of rejecting when no valid credentials
+/// are presented. Used by routes that personalize their response for a logged-in viewer
+/// (e.g. applying "friends"-tier privacy) but remain accessible anonymously.
+pub struct OptionalAuthUser(pub Option<AuthUser>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for OptionalAuthUser
+where
+    S: Send
+        + Sync
+        + AsRef<crate::auth::JwtConfig>
+        + AsRef<PgPool>
+        + AsRef<BlockedUserCache>
+        + AsRef<AuthMetrics>,
+{
+    type Rejection = std::convert::Infallible;
 
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match AuthUser::from_request_parts(parts, state).await {
+            Ok(user) => Ok(OptionalAuthUser(Some(user))),
+            Err(_) => Ok(OptionalAuthUser(None)),
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use axum::http::{HeaderValue, Method};
+    use crate::auth::jwt::JwtKey;
     use crate::auth::JwtConfig;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
 
     struct TestState {
         jwt_config: JwtConfig,
+        // Never actually connected to: `connect_lazy` defers the connection until a query
+        // runs, and every test either pre-seeds `blocked_cache` or fails before reaching it.
+        db: PgPool,
+        blocked_cache: BlockedUserCache,
+        auth_metrics: AuthMetrics,
     }
 
     impl AsRef<JwtConfig> for TestState {
@@ -162,6 +437,24 @@ mod tests {
         }
     }
 
+    impl AsRef<PgPool> for TestState {
+        fn as_ref(&self) -> &PgPool {
+            &self.db
+        }
+    }
+
+    impl AsRef<BlockedUserCache> for TestState {
+        fn as_ref(&self) -> &BlockedUserCache {
+            &self.blocked_cache
+        }
+    }
+
+    impl AsRef<AuthMetrics> for TestState {
+        fn as_ref(&self) -> &AuthMetrics {
+            &self.auth_metrics
+        }
+    }
+
     fn create_test_parts() -> Parts {
         let request = axum::http::Request::builder()
             .method(Method::GET)
@@ -175,10 +468,62 @@ mod tests {
     fn create_test_state() -> TestState {
         TestState {
             jwt_config: JwtConfig {
-                secret: "test-secret".to_string(),
+                primary: JwtKey {
+                    kid: "test".to_string(),
+                    algorithm: jsonwebtoken::Algorithm::HS256,
+                    secret: "test-secret".to_string(),
+                },
+                retired: Vec::new(),
                 expiration_hours: 24,
-                algorithm: jsonwebtoken::Algorithm::HS256,
-            }
+                expiration_minutes: None,
+                refresh_expiration_days: 30,
+            },
+            db: PgPool::connect_lazy("postgres://localhost/nonexistent")
+                .expect("lazy pool construction never touches the network"),
+            blocked_cache: BlockedUserCache::default(),
+            auth_metrics: AuthMetrics::default(),
+        }
+    }
+
+    /// A minimal `test-log`-style subscriber layer: instead of asserting on formatted log
+    /// lines, tests assert on the structured fields of emitted events, matching how
+    /// `authenticate` reports outcomes.
+    #[derive(Clone, Default)]
+    struct CapturingLayer {
+        events: Arc<Mutex<Vec<HashMap<String, String>>>>,
+    }
+
+    struct FieldVisitor(HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{value:?}"));
+        }
+
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for CapturingLayer {
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut visitor = FieldVisitor(HashMap::new());
+            event.record(&mut visitor);
+            self.events.lock().unwrap().push(visitor.0);
+        }
+    }
+
+    impl CapturingLayer {
+        fn field(&self, key: &str) -> Option<String> {
+            self.events
+                .lock()
+                .unwrap()
+                .iter()
+                .find_map(|fields| fields.get(key).cloned())
         }
     }
 
@@ -219,4 +564,190 @@ mod tests {
         // Should fail on JWT validation, not format validation
         assert!(matches!(result, Err(AuthError::InvalidToken)));
     }
+
+    #[tokio::test]
+    async fn test_falls_back_to_access_token_cookie_when_header_absent() {
+        let mut parts = create_test_parts();
+        let state = create_test_state();
+        let user_id = Uuid::new_v4();
+        state.blocked_cache.set(user_id, false);
+        let token = crate::auth::jwt::create_token(
+            user_id,
+            "test@example.com".to_string(),
+            "testuser".to_string(),
+            &state.jwt_config,
+        )
+        .unwrap();
+        parts.headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(&format!("{ACCESS_TOKEN_COOKIE_NAME}={token}")).unwrap(),
+        );
+        let result = RequireAuth::from_request_parts(&mut parts, &state).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_blocked_user_is_rejected_even_with_valid_token() {
+        let mut parts = create_test_parts();
+        let state = create_test_state();
+        let user_id = Uuid::new_v4();
+        state.blocked_cache.set(user_id, true);
+        let token = crate::auth::jwt::create_token(
+            user_id,
+            "test@example.com".to_string(),
+            "testuser".to_string(),
+            &state.jwt_config,
+        )
+        .unwrap();
+        parts.headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+        let result = AuthUser::from_request_parts(&mut parts, &state).await;
+        assert!(matches!(result, Err(AuthError::Blocked)));
+    }
+
+    #[tokio::test]
+    async fn test_auth_verify_emits_structured_success_outcome() {
+        let layer = CapturingLayer::default();
+        let subscriber = tracing_subscriber::registry().with(layer.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut parts = create_test_parts();
+        let state = create_test_state();
+        let user_id = Uuid::new_v4();
+        state.blocked_cache.set(user_id, false);
+        let token = crate::auth::jwt::create_token(
+            user_id,
+            "test@example.com".to_string(),
+            "testuser".to_string(),
+            &state.jwt_config,
+        )
+        .unwrap();
+        parts.headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+
+        assert!(AuthUser::from_request_parts(&mut parts, &state).await.is_ok());
+
+        assert_eq!(layer.field("outcome"), Some("verified".to_string()));
+        assert_eq!(layer.field("kid"), Some(state.jwt_config.primary.kid.clone()));
+    }
+
+    #[tokio::test]
+    async fn test_auth_verify_emits_structured_expired_outcome() {
+        let layer = CapturingLayer::default();
+        let subscriber = tracing_subscriber::registry().with(layer.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut parts = create_test_parts();
+        let mut state = create_test_state();
+        state.jwt_config.expiration_hours = -1;
+        let token = crate::auth::jwt::create_token(
+            Uuid::new_v4(),
+            "test@example.com".to_string(),
+            "testuser".to_string(),
+            &state.jwt_config,
+        )
+        .unwrap();
+        parts.headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+
+        let result = AuthUser::from_request_parts(&mut parts, &state).await;
+        assert!(matches!(result, Err(AuthError::ExpiredToken)));
+        assert_eq!(layer.field("outcome"), Some("expired".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_header_is_not_overridden_by_cookie() {
+        let mut parts = create_test_parts();
+        let state = create_test_state();
+        let token = crate::auth::jwt::create_token(
+            Uuid::new_v4(),
+            "test@example.com".to_string(),
+            "testuser".to_string(),
+            &state.jwt_config,
+        )
+        .unwrap();
+        parts.headers.insert("Authorization", HeaderValue::from_static("InvalidFormat"));
+        parts.headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(&format!("{ACCESS_TOKEN_COOKIE_NAME}={token}")).unwrap(),
+        );
+        let result = RequireAuth::from_request_parts(&mut parts, &state).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+    }
+
+    #[tokio::test]
+    async fn test_basic_credentials_missing_header() {
+        let mut parts = create_test_parts();
+        let result = BasicCredentials::from_request_parts(&mut parts, &create_test_state()).await;
+        assert!(matches!(result, Err(AuthError::MissingToken)));
+    }
+
+    #[tokio::test]
+    async fn test_basic_credentials_rejects_bearer_scheme() {
+        let mut parts = create_test_parts();
+        parts.headers.insert("Authorization", HeaderValue::from_static("Bearer some-token"));
+        let result = BasicCredentials::from_request_parts(&mut parts, &create_test_state()).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+    }
+
+    #[tokio::test]
+    async fn test_basic_credentials_decodes_email_and_password() {
+        let mut parts = create_test_parts();
+        let encoded = STANDARD.encode("climber@example.com:hunter2");
+        parts.headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Basic {encoded}")).unwrap(),
+        );
+        let creds = BasicCredentials::from_request_parts(&mut parts, &create_test_state())
+            .await
+            .unwrap();
+        assert_eq!(creds.email, "climber@example.com");
+        assert_eq!(creds.password, "hunter2");
+    }
+
+    #[tokio::test]
+    async fn test_basic_credentials_rejects_missing_colon() {
+        let mut parts = create_test_parts();
+        let encoded = STANDARD.encode("no-colon-here");
+        parts.headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Basic {encoded}")).unwrap(),
+        );
+        let result = BasicCredentials::from_request_parts(&mut parts, &create_test_state()).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+    }
+
+    #[tokio::test]
+    async fn test_claims_extractor_rejects_missing_token() {
+        let mut parts = create_test_parts();
+        let state = create_test_state();
+        let result = Claims::from_request_parts(&mut parts, &state).await;
+        assert!(matches!(result, Err(AppError::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn test_claims_extractor_rejects_expired_token() {
+        let mut parts = create_test_parts();
+        let mut state = create_test_state();
+        state.jwt_config.expiration_hours = -1;
+        let token = crate::auth::jwt::create_token(
+            Uuid::new_v4(),
+            "test@example.com".to_string(),
+            "testuser".to_string(),
+            &state.jwt_config,
+        )
+        .unwrap();
+        parts.headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+        let result = Claims::from_request_parts(&mut parts, &state).await;
+        assert!(matches!(result, Err(AppError::Auth(_))));
+    }
 }
\ No newline at end of file
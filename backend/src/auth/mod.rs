@@ -1,5 +1,11 @@
 pub mod jwt;
 pub mod middleware;
+pub mod oauth;
+pub mod password;
+pub mod totp;
 
-pub use jwt::{Claims, JwtConfig};
-pub use middleware::{AuthUser, RequireAuth};
\ No newline at end of file
+pub use jwt::{Claims, JwtConfig, JwtKey};
+pub use middleware::{
+    AuthUser, BasicCredentials, OptionalAuthUser, RequireAuth, StaffUser, VerifiedUser,
+};
+pub use oauth::OAuthConfig;
\ No newline at end of file
@@ -0,0 +1,152 @@
+//! Generic OAuth2 authorization-code flow for social login: build the provider's authorize
+//! URL, exchange the returned code for an access token, and fetch userinfo. Handler-level
+//! orchestration (CSRF `state` cookie, upsert into `UserRepository`, session issuance) lives
+//! in `handlers::oauth`; this module only knows how to talk to the provider.
+
+use reqwest::Url;
+use serde::Deserialize;
+use std::env;
+
+use crate::error::AppError;
+
+/// Endpoints and credentials for a single configured OAuth2 provider.
+#[derive(Debug, Clone)]
+pub struct OAuthConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub authorize_url: String,
+    pub token_url: String,
+    pub userinfo_url: String,
+    /// Space-separated scopes requested at authorization time; must be enough to get back an
+    /// email, a name, and an avatar from `userinfo_url`.
+    pub scope: String,
+}
+
+impl OAuthConfig {
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            client_id: env::var("OAUTH_CLIENT_ID")?,
+            client_secret: env::var("OAUTH_CLIENT_SECRET")?,
+            redirect_uri: env::var("OAUTH_REDIRECT_URI")?,
+            authorize_url: env::var("OAUTH_AUTHORIZE_URL")?,
+            token_url: env::var("OAUTH_TOKEN_URL")?,
+            userinfo_url: env::var("OAUTH_USERINFO_URL")?,
+            scope: env::var("OAUTH_SCOPE").unwrap_or_else(|_| "openid email profile".to_string()),
+        })
+    }
+
+    /// The URL to redirect the browser to, carrying `csrf_state` so the callback can confirm
+    /// the response corresponds to a request this instance actually issued.
+    pub fn authorize_url(&self, csrf_state: &str) -> Result<String, AppError> {
+        let mut url = Url::parse(&self.authorize_url)
+            .map_err(|e| AppError::OAuth(format!("invalid authorize URL: {e}")))?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &self.redirect_uri)
+            .append_pair("scope", &self.scope)
+            .append_pair("state", csrf_state);
+        Ok(url.to_string())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+/// The subset of userinfo claims this crate needs to upsert a local account.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OAuthUserInfo {
+    pub email: String,
+    /// Whether the provider itself has verified control of `email`. Absent on providers that
+    /// don't send it, which this treats as unverified rather than assuming the best.
+    #[serde(default)]
+    pub email_verified: bool,
+    pub name: Option<String>,
+    /// Mirrors the `picture` claim most OIDC-compatible providers return.
+    #[serde(rename = "picture")]
+    pub avatar: Option<String>,
+}
+
+/// Exchange an authorization `code` for an access token.
+pub async fn exchange_code(config: &OAuthConfig, code: &str) -> Result<String, AppError> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&config.token_url)
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &config.redirect_uri),
+            ("client_id", &config.client_id),
+            ("client_secret", &config.client_secret),
+        ])
+        .send()
+        .await
+        .map_err(|e| AppError::OAuth(format!("token exchange request failed: {e}")))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::OAuth(format!(
+            "token exchange failed with status {}",
+            response.status()
+        )));
+    }
+
+    let token: TokenResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::OAuth(format!("malformed token response: {e}")))?;
+
+    Ok(token.access_token)
+}
+
+/// Fetch the authenticated user's profile from the provider's userinfo endpoint.
+pub async fn fetch_userinfo(config: &OAuthConfig, access_token: &str) -> Result<OAuthUserInfo, AppError> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(&config.userinfo_url)
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .map_err(|e| AppError::OAuth(format!("userinfo request failed: {e}")))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::OAuth(format!(
+            "userinfo request failed with status {}",
+            response.status()
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::OAuth(format!("malformed userinfo response: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> OAuthConfig {
+        OAuthConfig {
+            client_id: "test-client".to_string(),
+            client_secret: "test-secret".to_string(),
+            redirect_uri: "https://example.com/auth/oauth/callback".to_string(),
+            authorize_url: "https://provider.example.com/oauth/authorize".to_string(),
+            token_url: "https://provider.example.com/oauth/token".to_string(),
+            userinfo_url: "https://provider.example.com/oauth/userinfo".to_string(),
+            scope: "openid email profile".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_authorize_url_carries_client_id_and_state() {
+        let config = test_config();
+        let url = config.authorize_url("csrf-abc123").unwrap();
+        assert!(url.starts_with("https://provider.example.com/oauth/authorize?"));
+        assert!(url.contains("client_id=test-client"));
+        assert!(url.contains("state=csrf-abc123"));
+        assert!(url.contains("redirect_uri="));
+    }
+}
@@ -0,0 +1,102 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use rand::RngCore;
+
+use crate::error::AppError;
+
+/// Byte length of a [`random_token`], chosen so the base64url encoding comfortably clears the
+/// 20-character minimum requested for one-off tokens (e.g. password reset links).
+const RANDOM_TOKEN_BYTES: usize = 24;
+
+/// Minimum password length enforced at registration and password-change time.
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Hash a plaintext password into a PHC-format Argon2id string (`$argon2id$...`), with a
+/// fresh per-password salt drawn from an OS RNG.
+pub fn hash_password(plaintext: &str) -> Result<String, AppError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(plaintext.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| {
+            tracing::error!("failed to hash password: {e}");
+            AppError::Internal
+        })
+}
+
+/// Verify a plaintext password against a stored PHC-format hash, in constant time.
+///
+/// An empty `hash` means the account has no password set (e.g. it was created through OAuth,
+/// see `handlers::oauth::oauth_callback`) rather than that something went wrong, so it's
+/// treated as "no match" instead of an invalid-hash error.
+pub fn verify_password(plaintext: &str, hash: &str) -> Result<bool, AppError> {
+    if hash.is_empty() {
+        return Ok(false);
+    }
+
+    let parsed_hash = PasswordHash::new(hash).map_err(|e| {
+        tracing::error!("stored password hash is invalid: {e}");
+        AppError::Internal
+    })?;
+
+    Ok(Argon2::default()
+        .verify_password(plaintext.as_bytes(), &parsed_hash)
+        .is_ok())
+}
+
+/// A minimum-length strength check. Real strength estimation (zxcvbn-style) is a larger
+/// dependency than this crate currently pulls in; length is the cheap first gate.
+pub fn is_strong_enough(plaintext: &str) -> bool {
+    plaintext.len() >= MIN_PASSWORD_LENGTH
+}
+
+/// A cryptographically-random, URL-safe token of at least 20 characters, for one-off
+/// credentials such as password reset links that aren't themselves hashed with Argon2.
+pub fn random_token() -> String {
+    let mut bytes = [0u8; RANDOM_TOKEN_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_and_verify_roundtrip() {
+        let hash = hash_password("correct horse battery staple").unwrap();
+        assert!(verify_password("correct horse battery staple", &hash).unwrap());
+        assert!(!verify_password("wrong password", &hash).unwrap());
+    }
+
+    #[test]
+    fn test_hashes_are_salted() {
+        let first = hash_password("same-password").unwrap();
+        let second = hash_password("same-password").unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_strength_check() {
+        assert!(!is_strong_enough("short"));
+        assert!(is_strong_enough("long-enough"));
+    }
+
+    #[test]
+    fn test_hash_and_verify_random_password() {
+        let password = random_token();
+        let hash = hash_password(&password).unwrap();
+        assert!(verify_password(&password, &hash).unwrap());
+    }
+
+    #[test]
+    fn test_random_token_unique_and_length_bounded() {
+        let tokens: std::collections::HashSet<String> =
+            (0..10_000).map(|_| random_token()).collect();
+        assert_eq!(tokens.len(), 10_000);
+        assert!(tokens.iter().all(|t| t.len() >= 20));
+    }
+}
@@ -0,0 +1,179 @@
+//! RFC 6238 TOTP (time-based one-time password) two-factor authentication.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, OsRng as AesOsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+use std::env;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// Load the symmetric key used to encrypt TOTP secrets at rest, from `TOTP_ENCRYPTION_KEY`
+/// (32 raw bytes, base64-encoded). Falls back to a fixed dev key, same as `JwtConfig`'s
+/// handling of a missing `JWT_SECRET`.
+pub fn encryption_key_from_env() -> [u8; 32] {
+    env::var("TOTP_ENCRYPTION_KEY")
+        .ok()
+        .and_then(|encoded| STANDARD.decode(encoded).ok())
+        .and_then(|bytes| bytes.try_into().ok())
+        .unwrap_or_else(|| {
+            tracing::warn!("TOTP_ENCRYPTION_KEY not set, using default (not secure for production)");
+            *b"totp-dev-key-change-in-prod!!!!!"
+        })
+}
+
+/// Encrypt a TOTP secret for storage, returning a base64 blob of `nonce || ciphertext`.
+pub fn seal_secret(secret: &[u8], key: &[u8; 32]) -> String {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let mut nonce_bytes = [0u8; 12];
+    AesOsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, secret)
+        .expect("AES-GCM encryption of a short secret cannot fail");
+
+    let mut blob = nonce_bytes.to_vec();
+    blob.extend_from_slice(&ciphertext);
+    STANDARD.encode(blob)
+}
+
+/// Decrypt a TOTP secret sealed by [`seal_secret`].
+pub fn open_secret(sealed: &str, key: &[u8; 32]) -> Option<Vec<u8>> {
+    let blob = STANDARD.decode(sealed).ok()?;
+    if blob.len() < 12 {
+        return None;
+    }
+    let (nonce_bytes, ciphertext) = blob.split_at(12);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher.decrypt(Nonce::from_slice(nonce_bytes), ciphertext).ok()
+}
+
+/// Length of a generated TOTP secret, in bytes, per the RFC 6238 recommendation for SHA-1.
+const SECRET_LENGTH: usize = 20;
+/// Time-step size in seconds.
+const STEP_SECONDS: u64 = 30;
+/// Number of adjacent time steps (each direction) tolerated for clock skew.
+const SKEW_STEPS: i64 = 1;
+
+/// Generate a new random TOTP secret.
+pub fn generate_secret() -> [u8; SECRET_LENGTH] {
+    let mut secret = [0u8; SECRET_LENGTH];
+    rand::thread_rng().fill_bytes(&mut secret);
+    secret
+}
+
+/// Base32-encode a secret for display/entry into an authenticator app.
+pub fn encode_secret(secret: &[u8]) -> String {
+    BASE32_NOPAD.encode(secret)
+}
+
+/// Decode a base32-encoded secret back into raw bytes.
+pub fn decode_secret(encoded: &str) -> Option<Vec<u8>> {
+    BASE32_NOPAD.decode(encoded.as_bytes()).ok()
+}
+
+/// Build the `otpauth://` enrollment URI for a QR code.
+pub fn otpauth_uri(issuer: &str, account: &str, secret: &[u8]) -> String {
+    let encoded_secret = encode_secret(secret);
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={encoded_secret}&issuer={issuer}&algorithm=SHA1&digits=6&period={STEP_SECONDS}"
+    )
+}
+
+/// Compute the 6-digit TOTP code for a given time step, per RFC 4226/6238.
+fn code_at_step(secret: &[u8], step: u64) -> u32 {
+    let mut mac = HmacSha1::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(&step.to_be_bytes());
+    let hmac_result = mac.finalize().into_bytes();
+
+    let offset = (hmac_result[19] & 0x0f) as usize;
+    let truncated = u32::from_be_bytes([
+        hmac_result[offset] & 0x7f,
+        hmac_result[offset + 1],
+        hmac_result[offset + 2],
+        hmac_result[offset + 3],
+    ]);
+
+    truncated % 1_000_000
+}
+
+/// Verify a submitted 6-digit code against the current time step, tolerating `SKEW_STEPS` of
+/// clock drift in either direction.
+pub fn verify_code(secret: &[u8], code: &str, unix_now: u64) -> bool {
+    let Ok(submitted) = code.parse::<u32>() else {
+        return false;
+    };
+    let current_step = (unix_now / STEP_SECONDS) as i64;
+
+    (-SKEW_STEPS..=SKEW_STEPS).any(|skew| {
+        let step = current_step + skew;
+        step >= 0 && code_at_step(secret, step as u64) == submitted
+    })
+}
+
+/// Generate `count` single-use recovery codes (plaintext, to be shown once and hashed for
+/// storage by the caller).
+pub fn generate_recovery_codes(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|_| {
+            let mut bytes = [0u8; 10];
+            rand::thread_rng().fill_bytes(&mut bytes);
+            BASE32_NOPAD.encode(&bytes)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_is_deterministic_within_a_step() {
+        let secret = generate_secret();
+        let now = 1_700_000_000u64;
+        assert_eq!(code_at_step(&secret, now / STEP_SECONDS), code_at_step(&secret, now / STEP_SECONDS));
+    }
+
+    #[test]
+    fn test_verify_accepts_current_step() {
+        let secret = generate_secret();
+        let now = 1_700_000_000u64;
+        let code = code_at_step(&secret, now / STEP_SECONDS);
+        assert!(verify_code(&secret, &format!("{code:06}"), now));
+    }
+
+    #[test]
+    fn test_verify_tolerates_one_step_of_skew() {
+        let secret = generate_secret();
+        let now = 1_700_000_000u64;
+        let next_step_code = code_at_step(&secret, now / STEP_SECONDS + 1);
+        assert!(verify_code(&secret, &format!("{next_step_code:06}"), now));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_code() {
+        let secret = generate_secret();
+        assert!(!verify_code(&secret, "000000", 1_700_000_000));
+    }
+
+    #[test]
+    fn test_seal_and_open_secret_roundtrip() {
+        let key = encryption_key_from_env();
+        let secret = generate_secret();
+        let sealed = seal_secret(&secret, &key);
+        assert_eq!(open_secret(&sealed, &key).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_recovery_codes_are_unique() {
+        let codes = generate_recovery_codes(10);
+        let unique: std::collections::HashSet<_> = codes.iter().collect();
+        assert_eq!(unique.len(), codes.len());
+    }
+}
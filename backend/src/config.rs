@@ -1,4 +1,6 @@
+use crate::csrf::CsrfConfig;
 use crate::database::connection::DatabaseConfig;
+use crate::logging::LogFormat;
 use std::env;
 use tracing::warn;
 
@@ -8,6 +10,11 @@ pub struct AppConfig {
     pub server_host: String,
     pub server_port: u16,
     pub log_level: String,
+    /// Output format for the `tracing` subscriber installed in `main`; see `logging::init`.
+    pub log_format: LogFormat,
+    /// This instance's externally-reachable origin, used to build ActivityPub actor URLs.
+    pub federation_base_url: String,
+    pub csrf: CsrfConfig,
 }
 
 impl AppConfig {
@@ -30,11 +37,19 @@ impl AppConfig {
         let log_level = env::var("RUST_LOG")
             .unwrap_or_else(|_| "info".to_string());
 
+        let log_format = LogFormat::from_env();
+
+        let federation_base_url = env::var("FEDERATION_BASE_URL")
+            .unwrap_or_else(|_| "http://localhost:3000".to_string());
+
         Ok(Self {
             database,
             server_host,
             server_port,
             log_level,
+            log_format,
+            federation_base_url,
+            csrf: CsrfConfig::from_env(),
         })
     }
 
@@ -0,0 +1,288 @@
+//! Double-submit-token CSRF protection. A safe request (anything outside
+//! `CsrfConfig::protected_methods`) is issued a random token: stored in a signed, `HttpOnly`
+//! cookie so it can't be read or forged cross-site, and echoed once in a plain response header
+//! so same-origin client code can capture it and attach it to later requests. A protected
+//! request must present that same token in the request header; a cross-site form or `fetch`
+//! can read neither the cookie nor the header of a response it didn't make, so it can't
+//! reproduce a match.
+
+use std::{collections::HashSet, env, sync::Arc};
+
+use axum::{
+    extract::{Request, State},
+    http::{
+        header::{HeaderName, COOKIE, SET_COOKIE},
+        HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde_json::json;
+use sha2::Sha256;
+use tracing::warn;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Byte length of the random token minted per CSRF cookie.
+const TOKEN_BYTES: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct CsrfConfig {
+    pub cookie_name: String,
+    pub header_name: HeaderName,
+    pub protected_methods: HashSet<Method>,
+    /// Path prefixes left out of CSRF handling entirely (neither checked nor issued a
+    /// cookie/header). Server-to-server traffic authenticated another way — ActivityPub's HTTP
+    /// Signatures under `/ap` — can never present this instance's cookie, so it has to be
+    /// exempt rather than rejected.
+    pub exempt_prefixes: Vec<String>,
+    /// Exact paths where a protected-method request is let through without a pre-existing
+    /// cookie: the unauthenticated entry points (login, registration) that mint a client's
+    /// first session rather than act on one it's assumed to already have. A cookie is still
+    /// issued on the response so the client's next mutating request carries one.
+    pub bootstrap_paths: HashSet<String>,
+    /// Used to sign the cookie so a cookie an attacker managed to set (e.g. from a sibling
+    /// subdomain) can't be paired with a matching header of the attacker's choosing.
+    secret: String,
+}
+
+impl CsrfConfig {
+    pub fn from_env() -> Self {
+        let cookie_name = env::var("CSRF_COOKIE_NAME").unwrap_or_else(|_| "csrf_token".to_string());
+
+        let header_name = env::var("CSRF_HEADER_NAME")
+            .ok()
+            .and_then(|raw| HeaderName::from_bytes(raw.as_bytes()).ok())
+            .unwrap_or_else(|| HeaderName::from_static("x-csrf-token"));
+
+        let protected_methods = env::var("CSRF_PROTECTED_METHODS")
+            .ok()
+            .map(|raw| parse_methods(&raw))
+            .filter(|methods| !methods.is_empty())
+            .unwrap_or_else(default_protected_methods);
+
+        let exempt_prefixes = env::var("CSRF_EXEMPT_PREFIXES")
+            .ok()
+            .map(|raw| parse_csv(&raw))
+            .filter(|prefixes| !prefixes.is_empty())
+            .unwrap_or_else(default_exempt_prefixes);
+
+        let bootstrap_paths = env::var("CSRF_BOOTSTRAP_PATHS")
+            .ok()
+            .map(|raw| parse_csv(&raw).into_iter().collect())
+            .filter(|paths: &HashSet<String>| !paths.is_empty())
+            .unwrap_or_else(default_bootstrap_paths);
+
+        let secret = env::var("CSRF_SECRET").unwrap_or_else(|_| {
+            warn!(
+                "CSRF_SECRET not set; generating an ephemeral per-process secret, which \
+                 invalidates every outstanding CSRF cookie on restart"
+            );
+            generate_token()
+        });
+
+        Self {
+            cookie_name,
+            header_name,
+            protected_methods,
+            exempt_prefixes,
+            bootstrap_paths,
+            secret,
+        }
+    }
+}
+
+fn default_protected_methods() -> HashSet<Method> {
+    [Method::POST, Method::PUT, Method::PATCH, Method::DELETE]
+        .into_iter()
+        .collect()
+}
+
+fn default_exempt_prefixes() -> Vec<String> {
+    vec!["/ap/".to_string(), "/.well-known/".to_string()]
+}
+
+fn default_bootstrap_paths() -> HashSet<String> {
+    ["/auth/login", "/auth/login/basic", "/users"]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_methods(raw: &str) -> HashSet<Method> {
+    raw.split(',')
+        .filter_map(|m| Method::from_bytes(m.trim().as_bytes()).ok())
+        .collect()
+}
+
+fn parse_csv(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Proves "minted by this process holding `secret`", matching the `Hmac<Sha1>` pattern in
+/// `auth::totp`.
+fn sign(secret: &str, token: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(token.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn mint_cookie_value(config: &CsrfConfig, token: &str) -> String {
+    format!("{token}.{}", sign(&config.secret, token))
+}
+
+/// Verify a `token.signature` cookie value, returning the token only if the signature matches.
+fn verify_cookie_value(config: &CsrfConfig, value: &str) -> Option<String> {
+    let (token, signature) = value.split_once('.')?;
+    (sign(&config.secret, token) == signature).then(|| token.to_string())
+}
+
+fn extract_cookie_token(config: &CsrfConfig, headers: &axum::http::HeaderMap) -> Option<String> {
+    let cookie_header = headers.get(COOKIE)?.to_str().ok()?;
+    let raw = cookie_header.split(';').find_map(|pair| {
+        let (name, value) = pair.trim().split_once('=')?;
+        (name == config.cookie_name).then(|| value.to_string())
+    })?;
+    verify_cookie_value(config, &raw)
+}
+
+fn csrf_rejection() -> Response {
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "CSRF token missing or invalid",
+            "code": StatusCode::FORBIDDEN.as_u16(),
+        })),
+    )
+        .into_response()
+}
+
+/// Attach a fresh cookie (signed) and header (plain token) to a response to a safe request,
+/// reusing the caller's already-valid token instead of rotating it on every request.
+fn attach_token(config: &CsrfConfig, token: &str, response: &mut Response) {
+    let cookie_value = mint_cookie_value(config, token);
+    if let Ok(cookie) = HeaderValue::from_str(&format!(
+        "{}={cookie_value}; Path=/; HttpOnly; SameSite=Strict",
+        config.cookie_name
+    )) {
+        response.headers_mut().insert(SET_COOKIE, cookie);
+    }
+    if let Ok(header) = HeaderValue::from_str(token) {
+        response.headers_mut().insert(config.header_name.clone(), header);
+    }
+}
+
+/// The CSRF middleware: `.layer(axum::middleware::from_fn_with_state(Arc::new(config),
+/// csrf::csrf_middleware))` alongside the CORS layer in `main`.
+pub async fn csrf_middleware(
+    State(config): State<Arc<CsrfConfig>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+
+    if config.exempt_prefixes.iter().any(|prefix| path.starts_with(prefix.as_str())) {
+        return next.run(req).await;
+    }
+
+    let is_bootstrap = config.bootstrap_paths.contains(path.as_str());
+    let existing_token = extract_cookie_token(&config, req.headers());
+
+    if config.protected_methods.contains(&method) && !is_bootstrap {
+        let header_token = req
+            .headers()
+            .get(&config.header_name)
+            .and_then(|value| value.to_str().ok());
+
+        let matches = matches!(
+            (&existing_token, header_token),
+            (Some(cookie_token), Some(header_token)) if cookie_token == header_token
+        );
+        if !matches {
+            return csrf_rejection();
+        }
+    }
+
+    let mut response = next.run(req).await;
+
+    if !config.protected_methods.contains(&method) || is_bootstrap {
+        let token = existing_token.unwrap_or_else(generate_token);
+        attach_token(&config, &token, &mut response);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> CsrfConfig {
+        CsrfConfig {
+            cookie_name: "csrf_token".to_string(),
+            header_name: HeaderName::from_static("x-csrf-token"),
+            protected_methods: default_protected_methods(),
+            exempt_prefixes: default_exempt_prefixes(),
+            bootstrap_paths: default_bootstrap_paths(),
+            secret: "test-secret".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cookie_round_trips_through_sign_and_verify() {
+        let config = test_config();
+        let token = generate_token();
+        let cookie_value = mint_cookie_value(&config, &token);
+
+        assert_eq!(verify_cookie_value(&config, &cookie_value), Some(token));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_token() {
+        let config = test_config();
+        let cookie_value = mint_cookie_value(&config, &generate_token());
+        let (_, signature) = cookie_value.split_once('.').unwrap();
+        let tampered = format!("{}.{signature}", generate_token());
+
+        assert_eq!(verify_cookie_value(&config, &tampered), None);
+    }
+
+    #[test]
+    fn test_default_protected_methods_cover_state_changing_verbs() {
+        let methods = default_protected_methods();
+        assert!(methods.contains(&Method::POST));
+        assert!(methods.contains(&Method::PUT));
+        assert!(methods.contains(&Method::PATCH));
+        assert!(methods.contains(&Method::DELETE));
+        assert!(!methods.contains(&Method::GET));
+    }
+
+    #[test]
+    fn test_default_exempt_prefixes_cover_federation_inbox() {
+        let prefixes = default_exempt_prefixes();
+        assert!(prefixes.iter().any(|p| "/ap/users/alex/inbox".starts_with(p.as_str())));
+    }
+
+    #[test]
+    fn test_default_bootstrap_paths_cover_login_and_register() {
+        let paths = default_bootstrap_paths();
+        assert!(paths.contains("/auth/login"));
+        assert!(paths.contains("/auth/login/basic"));
+        assert!(paths.contains("/users"));
+    }
+}
@@ -51,9 +51,15 @@ impl DatabaseConfig {
     }
 }
 
+/// Postgres is the only backend this crate speaks: the repository layer's `sqlx::query!`
+/// calls and every file under `migrations/` are Postgres-only (`UUID`, `JSONB`,
+/// `gen_random_uuid()`, `NOW()`). A from-scratch pluggable backend (e.g. running tests against
+/// an in-memory SQLite instance) would mean rewriting that query layer against a
+/// backend-generic abstraction such as `sqlx::Any`, which is a much larger change than a
+/// config knob — so `DatabaseConfig::url` only ever produces a `PgPool`.
 pub async fn create_pool(config: &DatabaseConfig) -> Result<PgPool, sqlx::Error> {
     info!("Connecting to database: {}", mask_password(&config.url));
-    
+
     let pool = PgPoolOptions::new()
         .max_connections(config.max_connections)
         .min_connections(config.min_connections)
@@ -92,4 +98,4 @@ fn mask_password(url: &str) -> String {
     } else {
         url.to_string()
     }
-}
\ No newline at end of file
+}
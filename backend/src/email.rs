@@ -0,0 +1,23 @@
+//! Dispatch of verification codes to a user's registered email address, behind a trait so the
+//! signup and re-request handlers don't hardcode a transport. The default implementation just
+//! logs, which is enough for local development; a real deployment supplies its own
+//! `VerificationEmailSender` (SES, SMTP, ...) when constructing `AppState`.
+
+use tracing::info;
+
+/// Sends a one-time verification code to an address. Implementations should treat `to_email`
+/// and `code` as sensitive: never log `code` at a level enabled in production, since logging it
+/// would defeat the point of emailing it out-of-band.
+pub trait VerificationEmailSender: Send + Sync {
+    fn send_verification_code(&self, to_email: &str, code: &str);
+}
+
+/// Logs the code instead of emailing it. The only implementation this crate ships; wire a real
+/// one in before deploying anywhere reachable by untrusted signups.
+pub struct LoggingEmailSender;
+
+impl VerificationEmailSender for LoggingEmailSender {
+    fn send_verification_code(&self, to_email: &str, code: &str) {
+        info!(to_email, code, "dispatching verification email (logging sender, not actually sent)");
+    }
+}
@@ -9,8 +9,8 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Database error: {0}")]
-    Database(#[from] sqlx::Error),
-    
+    Database(sqlx::Error),
+
     #[error("JSON serialization error: {0}")]
     Json(#[from] serde_json::Error),
     
@@ -25,7 +25,13 @@ pub enum AppError {
     
     #[error("Conflict: {0}")]
     Conflict(String),
-    
+
+    /// A step of the OAuth2 authorization-code flow failed: CSRF state mismatch, the
+    /// provider's token exchange, or its userinfo lookup. Reported as a gateway error since
+    /// the underlying failure is almost always the provider, not this instance.
+    #[error("OAuth error: {0}")]
+    OAuth(String),
+
     #[error("Internal server error")]
     Internal,
 }
@@ -39,6 +45,7 @@ impl IntoResponse for AppError {
             AppError::Validation(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
             AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            AppError::OAuth(msg) => (StatusCode::BAD_GATEWAY, msg.clone()),
             AppError::Internal => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()),
         };
 
@@ -52,4 +59,27 @@ impl IntoResponse for AppError {
 
         (status, body).into_response()
     }
-}
\ No newline at end of file
+}
+
+/// Turns a unique-constraint violation into a precise 409, so a racing duplicate insert (e.g.
+/// two concurrent signups for the same email) reports `Conflict` instead of a generic 500. All
+/// other database errors fall through to `AppError::Database` unchanged.
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        if let sqlx::Error::Database(ref db_err) = err {
+            if db_err.is_unique_violation() {
+                return match db_err.constraint() {
+                    Some("users_email_key") => {
+                        AppError::Conflict("email already in use".to_string())
+                    }
+                    Some("users_username_key") => {
+                        AppError::Conflict("username already taken".to_string())
+                    }
+                    _ => AppError::Database(err),
+                };
+            }
+        }
+
+        AppError::Database(err)
+    }
+}
@@ -0,0 +1,78 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, post, put},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{auth::StaffUser, error::AppError, repositories::user::UserRepository, state::AppState};
+
+#[derive(Debug, Deserialize)]
+pub struct SetBlockedRequest {
+    pub blocked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListUsersQuery {
+    #[serde(default = "default_list_users_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_list_users_limit() -> i64 {
+    50
+}
+
+pub fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/admin/users", get(list_users))
+        .route("/admin/users/:id/blocked", put(set_user_blocked))
+        .route("/admin/users/purge-expired", post(purge_expired_users))
+}
+
+/// List every account, paginated, for staff tooling. Staff-only, since it's the only way to
+/// enumerate users the crate exposes.
+async fn list_users(
+    _caller: StaffUser,
+    State(state): State<AppState>,
+    Query(query): Query<ListUsersQuery>,
+) -> Result<Json<Value>, AppError> {
+    let repo = UserRepository::new(state.db);
+    let users = repo.list_users(query.limit, query.offset).await?;
+
+    Ok(Json(json!({ "users": users })))
+}
+
+/// Block or unblock a user's account, invalidating `BlockedUserCache` so the change is
+/// enforced on the account's very next request rather than after the cache's TTL expires.
+async fn set_user_blocked(
+    _caller: StaffUser,
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<SetBlockedRequest>,
+) -> Result<Json<Value>, AppError> {
+    let repo = UserRepository::new(state.db.clone());
+    repo.set_blocked(user_id, request.blocked).await?;
+    state.blocked_users.invalidate(user_id);
+
+    Ok(Json(json!({ "id": user_id, "blocked": request.blocked })))
+}
+
+/// Hard-purge every soft-deleted account whose retention window has elapsed.
+async fn purge_expired_users(
+    _caller: StaffUser,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    let repo = UserRepository::new(state.db);
+    let purgeable = repo.find_purgeable(chrono::Utc::now()).await?;
+
+    for user_id in &purgeable {
+        repo.delete_user(*user_id).await?;
+    }
+
+    Ok(Json(json!({ "purged": purgeable.len() })))
+}
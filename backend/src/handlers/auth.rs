@@ -0,0 +1,288 @@
+use axum::{
+    http::{header, HeaderMap, HeaderValue},
+    response::Json,
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::{
+        jwt::{access_token_lifetime, hash_refresh_token, issue_token_pair},
+        middleware::ACCESS_TOKEN_COOKIE_NAME,
+        password::verify_password,
+        totp, AuthUser, BasicCredentials,
+    },
+    error::AppError,
+    models::user::LoginRequest,
+    repositories::{
+        refresh_token::RefreshTokenRepository, totp_recovery_code::TotpRecoveryCodeRepository,
+        user::UserRepository,
+    },
+    state::AppState,
+};
+
+const REFRESH_COOKIE_NAME: &str = "refresh_token";
+
+pub fn auth_routes() -> Router<AppState> {
+    Router::new()
+        .route("/auth/login", post(login))
+        .route("/auth/login/basic", post(login_basic))
+        .route("/auth/refresh", post(refresh))
+        .route("/auth/logout", post(logout))
+}
+
+/// Log in with email + password, returning an access token and setting the refresh cookie.
+async fn login(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(request): Json<LoginRequest>,
+) -> Result<(HeaderMap, Json<Value>), AppError> {
+    let repo = UserRepository::new(state.db.clone());
+
+    let user = repo.authenticate(&request.email, &request.password).await?;
+
+    if let Some((sealed_secret, enabled)) = repo.get_totp_state(user.id).await? {
+        if enabled {
+            verify_second_factor(state.db.clone(), user.id, &sealed_secret, &request).await?;
+        }
+    }
+
+    let (access_token, headers) =
+        issue_session(state.db, user.id, user.email, user.username, &state.jwt_config).await?;
+
+    Ok((headers, Json(json!({ "access_token": access_token }))))
+}
+
+/// Log in via HTTP Basic auth (`Authorization: Basic base64(email:password)`) instead of a
+/// JSON body. Plain credential verification only: accounts with 2FA enabled must use
+/// `/auth/login` instead, since there's no field in a Basic header to carry a TOTP code.
+async fn login_basic(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    credentials: BasicCredentials,
+) -> Result<(HeaderMap, Json<Value>), AppError> {
+    let repo = UserRepository::new(state.db.clone());
+
+    let (user_id, password_hash) = repo
+        .find_password_hash_by_email(&credentials.email)
+        .await?
+        .ok_or_else(|| AppError::Auth("Invalid email or password".to_string()))?;
+
+    if !verify_password(&credentials.password, &password_hash)? {
+        return Err(AppError::Auth("Invalid email or password".to_string()));
+    }
+
+    if let Some((_, enabled)) = repo.get_totp_state(user_id).await? {
+        if enabled {
+            return Err(AppError::Auth(
+                "Account requires two-factor authentication; use /auth/login instead".to_string(),
+            ));
+        }
+    }
+
+    let user = repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::Auth("Invalid email or password".to_string()))?;
+
+    let (access_token, headers) =
+        issue_session(state.db, user.id, user.email, user.username, &state.jwt_config).await?;
+
+    Ok((headers, Json(json!({ "access_token": access_token }))))
+}
+
+/// Require a valid TOTP code or unused recovery code once 2FA is enabled, as the last step
+/// of password login.
+async fn verify_second_factor(
+    db: PgPool,
+    user_id: Uuid,
+    sealed_secret: &str,
+    request: &LoginRequest,
+) -> Result<(), AppError> {
+    if let Some(recovery_code) = &request.recovery_code {
+        let accepted = TotpRecoveryCodeRepository::new(db)
+            .consume(user_id, &hash_refresh_token(recovery_code))
+            .await?;
+        return if accepted {
+            Ok(())
+        } else {
+            Err(AppError::Auth("Invalid recovery code".to_string()))
+        };
+    }
+
+    let code = request
+        .totp_code
+        .as_ref()
+        .ok_or_else(|| AppError::Auth("Two-factor code required".to_string()))?;
+
+    let secret = totp::open_secret(sealed_secret, &totp::encryption_key_from_env())
+        .ok_or(AppError::Internal)?;
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    if totp::verify_code(&secret, code, now) {
+        Ok(())
+    } else {
+        Err(AppError::Auth("Invalid two-factor code".to_string()))
+    }
+}
+
+/// Rotate a refresh token: the old row is revoked and a new access+refresh pair sharing the
+/// same `family_id` is issued. If the presented token was already revoked, treat it as theft
+/// and kill the entire family, forcing the client to log in again.
+async fn refresh(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    headers: HeaderMap,
+) -> Result<(HeaderMap, Json<Value>), AppError> {
+    let presented = extract_refresh_cookie(&headers)
+        .ok_or_else(|| AppError::Auth("Missing refresh token".to_string()))?;
+
+    let refresh_repo = RefreshTokenRepository::new(state.db.clone());
+    let token_hash = hash_refresh_token(&presented);
+
+    let stored = refresh_repo
+        .find_by_hash(&token_hash)
+        .await?
+        .ok_or_else(|| AppError::Auth("Invalid refresh token".to_string()))?;
+
+    if stored.revoked {
+        refresh_repo.revoke_family(stored.family_id).await?;
+        return Err(AppError::Auth(
+            "Refresh token reuse detected, session revoked".to_string(),
+        ));
+    }
+
+    if stored.expires_at < chrono::Utc::now() {
+        return Err(AppError::Auth("Refresh token expired".to_string()));
+    }
+
+    let user_repo = UserRepository::new(state.db.clone());
+    let user = user_repo
+        .find_by_id(stored.user_id)
+        .await?
+        .ok_or_else(|| AppError::Auth("User not found".to_string()))?;
+
+    refresh_repo.revoke(stored.id).await?;
+
+    let pair = issue_token_pair(
+        user.id,
+        user.email,
+        user.username,
+        stored.family_id,
+        &state.jwt_config,
+    )
+    .map_err(|_| AppError::Internal)?;
+
+    refresh_repo
+        .create(
+            user.id,
+            &pair.refresh_token.token_hash,
+            pair.refresh_token.family_id,
+            pair.refresh_token.expires_at,
+        )
+        .await?;
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.append(
+        header::SET_COOKIE,
+        refresh_cookie_header(&pair.refresh_token.token, pair.refresh_token.expires_at),
+    );
+    response_headers.append(
+        header::SET_COOKIE,
+        access_cookie_header(&pair.access_token, &state.jwt_config),
+    );
+
+    Ok((
+        response_headers,
+        Json(json!({ "access_token": pair.access_token })),
+    ))
+}
+
+/// Revoke every refresh token the caller holds (all families) and clear both the refresh and
+/// access cookies.
+async fn logout(
+    user: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> Result<HeaderMap, AppError> {
+    let refresh_repo = RefreshTokenRepository::new(state.db.clone());
+    refresh_repo.revoke_all_for_user(user.id).await?;
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.append(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&format!(
+            "{REFRESH_COOKIE_NAME}=; HttpOnly; Secure; SameSite=Strict; Path=/auth; Max-Age=0"
+        ))
+        .map_err(|_| AppError::Internal)?,
+    );
+    response_headers.append(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&format!(
+            "{ACCESS_TOKEN_COOKIE_NAME}=; HttpOnly; Secure; SameSite=Strict; Path=/; Max-Age=0"
+        ))
+        .map_err(|_| AppError::Internal)?,
+    );
+
+    Ok(response_headers)
+}
+
+fn extract_refresh_cookie(headers: &HeaderMap) -> Option<String> {
+    let cookie_header = headers.get(header::COOKIE)?.to_str().ok()?;
+    cookie_header.split(';').find_map(|pair| {
+        let (name, value) = pair.trim().split_once('=')?;
+        (name == REFRESH_COOKIE_NAME).then(|| value.to_string())
+    })
+}
+
+fn refresh_cookie_header(token: &str, expires_at: chrono::DateTime<chrono::Utc>) -> HeaderValue {
+    let max_age = (expires_at - chrono::Utc::now()).num_seconds().max(0);
+    HeaderValue::from_str(&format!(
+        "{REFRESH_COOKIE_NAME}={token}; HttpOnly; Secure; SameSite=Strict; Path=/auth; Max-Age={max_age}"
+    ))
+    .unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+/// Mirror the access token in a cookie (in addition to the response body) so browser clients
+/// that can't attach an `Authorization` header can still authenticate via `RequireAuth`/
+/// `AuthUser`'s cookie fallback.
+fn access_cookie_header(token: &str, jwt_config: &crate::auth::JwtConfig) -> HeaderValue {
+    let max_age = access_token_lifetime(jwt_config).num_seconds().max(0);
+    HeaderValue::from_str(&format!(
+        "{ACCESS_TOKEN_COOKIE_NAME}={token}; HttpOnly; Secure; SameSite=Strict; Path=/; Max-Age={max_age}"
+    ))
+    .unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+/// Issue the initial access+refresh pair for a freshly authenticated user, persisting the
+/// refresh token and returning the headers to attach its cookie to the response.
+pub async fn issue_session(
+    db: PgPool,
+    user_id: Uuid,
+    email: String,
+    username: String,
+    jwt_config: &crate::auth::JwtConfig,
+) -> Result<(String, HeaderMap), AppError> {
+    let pair = issue_token_pair(user_id, email, username, Uuid::new_v4(), jwt_config)
+        .map_err(|_| AppError::Internal)?;
+
+    RefreshTokenRepository::new(db)
+        .create(
+            user_id,
+            &pair.refresh_token.token_hash,
+            pair.refresh_token.family_id,
+            pair.refresh_token.expires_at,
+        )
+        .await?;
+
+    let mut headers = HeaderMap::new();
+    headers.append(
+        header::SET_COOKIE,
+        refresh_cookie_header(&pair.refresh_token.token, pair.refresh_token.expires_at),
+    );
+    headers.append(
+        header::SET_COOKIE,
+        access_cookie_header(&pair.access_token, jwt_config),
+    );
+
+    Ok((pair.access_token, headers))
+}
@@ -0,0 +1,114 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{get, post},
+    Router,
+};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use crate::{
+    auth::AuthUser,
+    error::AppError,
+    media::avatar::{process_avatar, AvatarError, AVATAR_SIZES},
+    repositories::user::UserRepository,
+    state::AppState,
+};
+
+pub fn avatar_routes() -> Router<AppState> {
+    Router::new()
+        .route("/users/me/avatar", post(upload_avatar))
+        .route("/avatars/:filename", get(serve_avatar))
+}
+
+fn avatar_storage_dir() -> PathBuf {
+    std::env::var("AVATAR_STORAGE_DIR")
+        .unwrap_or_else(|_| "./uploads/avatars".to_string())
+        .into()
+}
+
+impl From<AvatarError> for AppError {
+    fn from(err: AvatarError) -> Self {
+        AppError::Validation(err.to_string())
+    }
+}
+
+/// Upload a new avatar: sniffed, size-capped, re-encoded to square PNG thumbnails, and
+/// written to disk under a server-controlled path so `avatar_url` never trusts client input.
+async fn upload_avatar(
+    user: AuthUser,
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Json<Value>, AppError> {
+    let mut file_bytes: Option<Vec<u8>> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| AppError::Validation("Invalid multipart upload".to_string()))?
+    {
+        if field.name() == Some("avatar") {
+            file_bytes = Some(
+                field
+                    .bytes()
+                    .await
+                    .map_err(|_| AppError::Validation("Failed to read upload".to_string()))?
+                    .to_vec(),
+            );
+        }
+    }
+
+    let bytes = file_bytes.ok_or_else(|| AppError::Validation("Missing 'avatar' field".to_string()))?;
+    let thumbnails = process_avatar(&bytes)?;
+
+    let dir = avatar_storage_dir();
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|_| AppError::Internal)?;
+
+    let mut avatar_url = String::new();
+    for thumbnail in &thumbnails {
+        let filename = format!("{}_{}.png", user.id, thumbnail.size);
+        tokio::fs::write(dir.join(&filename), &thumbnail.png_bytes)
+            .await
+            .map_err(|_| AppError::Internal)?;
+
+        if thumbnail.size == AVATAR_SIZES[0] {
+            avatar_url = format!("/avatars/{filename}");
+        }
+    }
+
+    let repo = UserRepository::new(state.db);
+    let profile = repo
+        .get_profile(user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Profile not found".to_string()))?;
+    let mut profile_data = profile
+        .get_profile_data()
+        .map_err(|_| AppError::Internal)?;
+    profile_data.avatar_url = Some(avatar_url.clone());
+    repo.update_profile(user.id, profile_data).await?;
+
+    Ok(Json(json!({ "avatar_url": avatar_url })))
+}
+
+/// Serve a generated avatar thumbnail, with the content type resolved from its filename
+/// rather than trusted from storage.
+async fn serve_avatar(Path(filename): Path<String>) -> Result<Response, AppError> {
+    if filename.contains('/') || filename.contains("..") {
+        return Err(AppError::Validation("Invalid avatar filename".to_string()));
+    }
+
+    let path = avatar_storage_dir().join(&filename);
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|_| AppError::NotFound("Avatar not found".to_string()))?;
+
+    let mime = mime_guess::from_path(&path).first_or_octet_stream();
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, mime.as_ref().to_string())],
+        bytes,
+    )
+        .into_response())
+}
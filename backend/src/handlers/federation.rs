@@ -0,0 +1,207 @@
+//! ActivityPub surface: WebFinger discovery, the actor document, and the inbox/outbox/
+//! followers/following collections. Actor routes live under `/ap` rather than `/users/:id`
+//! so they don't collide with the Uuid-keyed REST profile routes in `handlers::user`.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, Method, Uri},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    error::AppError,
+    models::federation,
+    repositories::{federation::FederationRepository, user::UserRepository},
+    state::AppState,
+};
+
+pub fn federation_routes() -> Router<AppState> {
+    Router::new()
+        .route("/.well-known/webfinger", get(webfinger))
+        .route("/ap/users/:username", get(get_actor))
+        .route("/ap/users/:username/inbox", post(post_inbox))
+        .route("/ap/users/:username/outbox", get(get_outbox))
+        .route("/ap/users/:username/followers", get(get_followers))
+        .route("/ap/users/:username/following", get(get_following))
+}
+
+#[derive(Debug, Deserialize)]
+struct WebFingerQuery {
+    resource: String,
+}
+
+/// Resolve `acct:username@host` to this instance's actor URL.
+async fn webfinger(
+    State(state): State<AppState>,
+    Query(query): Query<WebFingerQuery>,
+) -> Result<Json<Value>, AppError> {
+    let (username, host) = federation::parse_acct_resource(&query.resource)
+        .ok_or_else(|| AppError::Validation("Invalid WebFinger resource".to_string()))?;
+
+    UserRepository::new(state.db)
+        .find_by_username(&username)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let response = federation::build_webfinger_response(&state.federation_base_url, &host, &username);
+    Ok(Json(serde_json::to_value(response)?))
+}
+
+async fn resolve_user_id(
+    state: &AppState,
+    username: &str,
+) -> Result<uuid::Uuid, AppError> {
+    UserRepository::new(state.db.clone())
+        .find_by_username(username)
+        .await?
+        .map(|user| user.id)
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+}
+
+/// Serve the actor document, generating a keypair for this user on first request.
+async fn get_actor(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> Result<Json<Value>, AppError> {
+    let user_id = resolve_user_id(&state, &username).await?;
+    let (public_key_pem, _private_key) = FederationRepository::new(state.db)
+        .ensure_keypair(user_id)
+        .await?;
+
+    let actor = federation::build_actor(&state.federation_base_url, &username, &public_key_pem);
+    Ok(Json(serde_json::to_value(actor)?))
+}
+
+/// The user's outbox, as an ActivityStreams `OrderedCollection`.
+async fn get_outbox(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> Result<Json<Value>, AppError> {
+    let user_id = resolve_user_id(&state, &username).await?;
+    let items = FederationRepository::new(state.db).list_outbox(user_id).await?;
+
+    Ok(Json(json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "OrderedCollection",
+        "totalItems": items.len(),
+        "orderedItems": items,
+    })))
+}
+
+/// Accepted remote followers, as an ActivityStreams `Collection`.
+async fn get_followers(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> Result<Json<Value>, AppError> {
+    let user_id = resolve_user_id(&state, &username).await?;
+    let followers = FederationRepository::new(state.db)
+        .list_accepted_followers(user_id)
+        .await?;
+
+    Ok(Json(json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "Collection",
+        "totalItems": followers.len(),
+        "items": followers,
+    })))
+}
+
+/// This crate doesn't track which remote actors a local user follows yet, only the reverse
+/// (`followers`), so this is always an empty collection.
+async fn get_following(Path(_username): Path<String>) -> Json<Value> {
+    Json(json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "Collection",
+        "totalItems": 0,
+        "items": [],
+    }))
+}
+
+/// Verify the HTTP Signature on an incoming activity against the claimed actor's published
+/// `publicKey`, so `post_inbox` never acts on an activity it can't prove came from `actor`.
+/// Returns the actor URL the signature actually verified against (the `keyId`'s owner), which
+/// the caller must treat as the only authenticated identity — never the body's unauthenticated
+/// `actor` field. Rejects with `AppError::Auth` (401) on anything that doesn't check out:
+/// missing/malformed `Signature` header, a `keyId` whose actor document can't be fetched, or a
+/// signature that doesn't verify against it.
+async fn verify_inbox_signature(
+    method: &Method,
+    uri: &Uri,
+    headers: &HeaderMap,
+) -> Result<String, AppError> {
+    let raw_signature = headers
+        .get("signature")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| AppError::Auth("missing Signature header".to_string()))?;
+
+    let parsed = federation::parse_signature_header(raw_signature)
+        .ok_or_else(|| AppError::Auth("malformed Signature header".to_string()))?;
+
+    let signing_string = federation::build_signing_string(
+        method.as_str(),
+        uri.path(),
+        |name| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string),
+        &parsed.headers,
+    )
+    .ok_or_else(|| AppError::Auth("Signature covers a header that wasn't sent".to_string()))?;
+
+    let actor_url = federation::actor_url_from_key_id(&parsed.key_id);
+    let public_key_pem = federation::fetch_remote_public_key(actor_url).await?;
+
+    if !federation::verify(&public_key_pem, signing_string.as_bytes(), &parsed.signature) {
+        return Err(AppError::Auth("Signature verification failed".to_string()));
+    }
+
+    Ok(actor_url.to_string())
+}
+
+/// Handle an incoming activity. The only activity type implemented so far is `Follow`, which
+/// is auto-accepted: the follower is recorded as accepted and an `Accept` is appended to the
+/// owner's outbox (stored unsigned, and not delivered to the follower's inbox). Anything else
+/// is acknowledged but otherwise ignored. A `Follow` must carry a `Signature` header that
+/// verifies against the claimed actor's published `publicKey` (see [`verify_inbox_signature`])
+/// before anything is touched in the repository; other activity types are acknowledged
+/// without a signature check.
+async fn post_inbox(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    Json(activity): Json<Value>,
+) -> Result<Json<Value>, AppError> {
+    let user_id = resolve_user_id(&state, &username).await?;
+
+    let activity_type = activity.get("type").and_then(Value::as_str);
+    let remote_actor = activity.get("actor").and_then(Value::as_str);
+
+    if let (Some("Follow"), Some(remote_actor)) = (activity_type, remote_actor) {
+        let verified_actor = verify_inbox_signature(&method, &uri, &headers).await?;
+        if verified_actor != remote_actor {
+            return Err(AppError::Auth(
+                "Signature keyId does not match the activity's actor".to_string(),
+            ));
+        }
+
+        let repo = FederationRepository::new(state.db.clone());
+        repo.add_follower(user_id, &verified_actor).await?;
+        repo.accept_follower(user_id, &verified_actor).await?;
+
+        let (_public_key_pem, private_key) = repo.ensure_keypair(user_id).await?;
+        let actor_url = federation::actor_url(&state.federation_base_url, &username);
+        let follow: federation::Activity = serde_json::from_value(activity)?;
+        let accept = federation::accept_activity(&state.federation_base_url, &actor_url, &follow);
+
+        // A signature over the Accept's id is computed here, but it is neither stored with the
+        // activity nor sent anywhere.
+        let _signature = federation::sign(&private_key, accept.id.as_bytes());
+
+        repo.append_outbox(user_id, &serde_json::to_value(&accept)?).await?;
+    }
+
+    Ok(Json(json!({ "status": "accepted" })))
+}
@@ -0,0 +1,6 @@
+pub mod admin;
+pub mod auth;
+pub mod avatar;
+pub mod federation;
+pub mod oauth;
+pub mod user;
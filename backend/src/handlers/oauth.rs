@@ -0,0 +1,189 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, HeaderValue},
+    response::{Json, Redirect},
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    auth::{
+        oauth::{self, OAuthConfig},
+        password::random_token,
+    },
+    error::AppError,
+    repositories::user::UserRepository,
+    state::AppState,
+};
+
+use super::auth::issue_session;
+
+const OAUTH_STATE_COOKIE_NAME: &str = "oauth_state";
+
+/// How long the CSRF `state` cookie lives, bounding how long a user can sit on the provider's
+/// consent screen before the callback is rejected.
+const OAUTH_STATE_MAX_AGE_SECS: i64 = 600;
+
+pub fn oauth_routes() -> Router<AppState> {
+    Router::new()
+        .route("/auth/oauth/authorize", get(oauth_authorize))
+        .route("/auth/oauth/callback", get(oauth_callback))
+}
+
+fn configured_oauth(state: &AppState) -> Result<std::sync::Arc<OAuthConfig>, AppError> {
+    state
+        .oauth_config
+        .clone()
+        .ok_or_else(|| AppError::OAuth("OAuth login is not configured".to_string()))
+}
+
+/// Redirect the browser to the provider's consent screen, carrying a fresh CSRF `state` that
+/// the callback verifies against the cookie set here.
+async fn oauth_authorize(State(state): State<AppState>) -> Result<(HeaderMap, Redirect), AppError> {
+    let oauth_config = configured_oauth(&state)?;
+    let csrf_state = random_token();
+    let authorize_url = oauth_config.authorize_url(&csrf_state)?;
+
+    let mut headers = HeaderMap::new();
+    headers.append(header::SET_COOKIE, oauth_state_cookie_header(&csrf_state));
+
+    Ok((headers, Redirect::to(&authorize_url)))
+}
+
+#[derive(Debug, Deserialize)]
+struct OAuthCallbackQuery {
+    code: Option<String>,
+    state: Option<String>,
+    /// Set instead of `code` when the user declines consent or the provider otherwise fails.
+    error: Option<String>,
+}
+
+/// Exchange the authorization code for a token, fetch userinfo, and upsert + log in the
+/// matching local account — issuing the same access+refresh pair as password login.
+async fn oauth_callback(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<OAuthCallbackQuery>,
+) -> Result<(HeaderMap, Json<Value>), AppError> {
+    if let Some(error) = query.error {
+        return Err(AppError::OAuth(format!("provider returned an error: {error}")));
+    }
+
+    let oauth_config = configured_oauth(&state)?;
+
+    let expected_state = extract_oauth_state_cookie(&headers)
+        .ok_or_else(|| AppError::OAuth("Missing OAuth state cookie".to_string()))?;
+    let presented_state = query
+        .state
+        .ok_or_else(|| AppError::OAuth("Missing state parameter".to_string()))?;
+    if presented_state != expected_state {
+        return Err(AppError::OAuth("OAuth state mismatch".to_string()));
+    }
+
+    let code = query
+        .code
+        .ok_or_else(|| AppError::OAuth("Missing code parameter".to_string()))?;
+
+    let access_token = oauth::exchange_code(&oauth_config, &code).await?;
+    let userinfo = oauth::fetch_userinfo(&oauth_config, &access_token).await?;
+
+    if !userinfo.email_verified {
+        return Err(AppError::OAuth(
+            "provider has not verified this email address".to_string(),
+        ));
+    }
+
+    let repo = UserRepository::new(state.db.clone());
+    let user = match repo.find_by_email(&userinfo.email).await? {
+        Some(user) => {
+            // A non-empty password_hash means the account was created through (or has since
+            // set up) password login, not this OAuth flow. Logging the OAuth caller in would
+            // let anyone who controls a verified address at the IdP take over an existing
+            // password-protected account just by matching its email.
+            if let Some((_, password_hash)) =
+                repo.find_password_hash_by_email(&userinfo.email).await?
+            {
+                if !password_hash.is_empty() {
+                    return Err(AppError::Conflict(
+                        "an account with this email already has a password set".to_string(),
+                    ));
+                }
+            }
+            user
+        }
+        None => {
+            let username = derive_unique_username(&repo, &userinfo.email).await?;
+            // No password login for an OAuth-created account yet, so leave the placeholder
+            // empty rather than hashing a token nobody will ever enter, matching the "unusable
+            // placeholder" convention `password_hash` was added under.
+            repo.create_user(
+                userinfo.email,
+                username,
+                String::new(),
+                userinfo.name,
+                userinfo.avatar,
+                None,
+            )
+            .await?
+        }
+    };
+
+    let (access_token, mut response_headers) =
+        issue_session(state.db, user.id, user.email, user.username, &state.jwt_config).await?;
+    response_headers.append(header::SET_COOKIE, clear_oauth_state_cookie_header());
+
+    Ok((response_headers, Json(json!({ "access_token": access_token }))))
+}
+
+/// Turn the local part of `email` into a candidate username, falling back to a
+/// random-suffixed variant (and finally an error) if it's already taken.
+async fn derive_unique_username(repo: &UserRepository, email: &str) -> Result<String, AppError> {
+    let base: String = email
+        .split('@')
+        .next()
+        .unwrap_or("user")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    let base = if base.is_empty() { "user".to_string() } else { base };
+
+    if !repo.username_exists(&base).await? {
+        return Ok(base);
+    }
+
+    for _ in 0..5 {
+        let candidate = format!("{base}-{}", &random_token()[..6]);
+        if !repo.username_exists(&candidate).await? {
+            return Ok(candidate);
+        }
+    }
+
+    Err(AppError::OAuth(
+        "could not derive a unique username for this account".to_string(),
+    ))
+}
+
+fn extract_oauth_state_cookie(headers: &HeaderMap) -> Option<String> {
+    let cookie_header = headers.get(header::COOKIE)?.to_str().ok()?;
+    cookie_header.split(';').find_map(|pair| {
+        let (name, value) = pair.trim().split_once('=')?;
+        (name == OAUTH_STATE_COOKIE_NAME).then(|| value.to_string())
+    })
+}
+
+fn oauth_state_cookie_header(csrf_state: &str) -> HeaderValue {
+    HeaderValue::from_str(&format!(
+        "{OAUTH_STATE_COOKIE_NAME}={csrf_state}; HttpOnly; Secure; SameSite=Lax; Path=/auth/oauth; Max-Age={OAUTH_STATE_MAX_AGE_SECS}"
+    ))
+    .unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+fn clear_oauth_state_cookie_header() -> HeaderValue {
+    HeaderValue::from_str(&format!(
+        "{OAUTH_STATE_COOKIE_NAME}=; HttpOnly; Secure; SameSite=Lax; Path=/auth/oauth; Max-Age=0"
+    ))
+    .unwrap_or_else(|_| HeaderValue::from_static(""))
+}
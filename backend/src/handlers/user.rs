@@ -8,55 +8,30 @@ use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::{
-    auth::AuthUser,
+    auth::{
+        password::{hash_password, is_strong_enough, verify_password, MIN_PASSWORD_LENGTH},
+        totp,
+        AuthUser, OptionalAuthUser,
+    },
     error::AppError,
-    models::user::{CreateUserRequest, UpdateUserRequest, ProfileData, PublicUser, PublicStatistics, UserStatistics, StatisticsData},
-    repositories::user::UserRepository,
+    models::{
+        relationship::ViewerRelation,
+        user::{
+            is_valid_email, ChangePasswordRequest, ConfirmEmailVerificationRequest,
+            CreateUserRequest, DisableTotpRequest, PublicUser, UpdateUserRequest,
+            VerifyTotpRequest,
+        },
+        verification::VerificationPurpose,
+    },
+    repositories::{
+        relationship::RelationshipRepository, totp_recovery_code::TotpRecoveryCodeRepository,
+        user::UserRepository, verification_otp::VerificationOtpRepository,
+    },
     state::AppState,
 };
 
-/// Helper function to create public statistics based on privacy settings
-fn create_public_statistics(
-    statistics: &UserStatistics,
-    statistics_data: &StatisticsData,
-    visibility: &str,
-) -> PublicStatistics {
-    match visibility {
-        "public" => PublicStatistics {
-            total_attempts: Some(statistics.total_attempts),
-            total_ascents: Some(statistics.total_ascents),
-            personal_best_grade: statistics.personal_best_grade.clone(),
-            grade_distribution: Some(statistics_data.grade_distribution.clone()),
-        },
-        _ => PublicStatistics {
-            total_attempts: None,
-            total_ascents: None,
-            personal_best_grade: None,
-            grade_distribution: None,
-        },
-    }
-}
-
-/// Helper function to filter profile data based on privacy settings for public access
-fn filter_profile_for_public(profile_data: &ProfileData) -> ProfileData {
-    match profile_data.privacy_settings.profile_visibility.as_str() {
-        "public" => profile_data.clone(),
-        "friends" => ProfileData {
-            first_name: None,
-            last_name: None,
-            display_name: profile_data.display_name.clone(),
-            bio: None,
-            avatar_url: profile_data.avatar_url.clone(),
-            location: None,
-            preferred_units: None,
-            privacy_settings: profile_data.privacy_settings.clone(),
-        },
-        _ => ProfileData {
-            display_name: Some("Private User".to_string()),
-            ..Default::default()
-        },
-    }
-}
+/// Number of recovery codes issued when 2FA is enabled.
+const RECOVERY_CODE_COUNT: usize = 10;
 
 pub fn user_routes() -> Router<AppState> {
     Router::new()
@@ -64,7 +39,20 @@ pub fn user_routes() -> Router<AppState> {
         .route("/users/me", get(get_current_user))
         .route("/users/me", put(update_current_user))
         .route("/users/me", delete(delete_current_user))
+        .route("/users/me/deactivate", post(deactivate_current_user))
+        .route("/users/me/reactivate", post(reactivate_current_user))
+        .route("/users/me/export", get(export_current_user_data))
+        .route("/users/me/password", put(change_password))
+        .route("/users/me/verify-email/request", post(request_email_verification))
+        .route("/users/me/verify-email/confirm", post(confirm_email_verification))
+        .route("/users/me/2fa/enable", post(enable_totp))
+        .route("/users/me/2fa/verify", post(verify_totp))
+        .route("/users/me/2fa/disable", post(disable_totp))
         .route("/users/:id", get(get_user_by_id))
+        .route("/users/:id/friend-request", post(send_friend_request))
+        .route("/users/:id/friend-accept", post(accept_friend_request))
+        .route("/users/:id/friend-reject", post(reject_friend_request))
+        .route("/users/:id/friend", delete(remove_friend))
 }
 
 /// Register a new user
@@ -73,27 +61,46 @@ async fn register_user(
     Json(request): Json<CreateUserRequest>,
 ) -> Result<Json<Value>, AppError> {
     // Fast-fail validation first (in-memory operations)
-    
-    // Validate email format (basic validation)
-    if !request.email.contains('@') {
-        return Err(AppError::BadRequest("Invalid email format".to_string()));
+
+    // Validate email format, the same regex `User::new` enforces
+    if !is_valid_email(&request.email) {
+        return Err(AppError::Validation("Invalid email format".to_string()));
     }
 
     // Validate username (basic validation)
     if request.username.len() < 3 || request.username.len() > 50 {
-        return Err(AppError::BadRequest("Username must be between 3 and 50 characters".to_string()));
+        return Err(AppError::Validation("Username must be between 3 and 50 characters".to_string()));
+    }
+
+    // Validate password strength
+    if !is_strong_enough(&request.password) {
+        return Err(AppError::Validation(format!(
+            "Password must be at least {MIN_PASSWORD_LENGTH} characters"
+        )));
     }
 
+    let password_hash = hash_password(&request.password)?;
+
     // Now perform database operations
-    let repo = UserRepository::new(state.db);
+    let repo = UserRepository::new(state.db.clone());
 
     // Create the user (this will check for duplicates inside a transaction)
     let user = repo.create_user(
         request.email,
         request.username,
+        password_hash,
+        request.name,
+        request.avatar,
         request.profile,
     ).await?;
 
+    // Kick off email verification immediately so the account isn't left unverified until the
+    // owner thinks to ask for a code.
+    let code = VerificationOtpRepository::new(state.db)
+        .issue(user.id, VerificationPurpose::EmailVerify)
+        .await?;
+    state.email_sender.send_verification_code(&user.email, &code);
+
     Ok(Json(json!({
         "message": "User registered successfully",
         "user": {
@@ -105,6 +112,169 @@ async fn register_user(
     })))
 }
 
+/// Change the current user's password, requiring the current password as proof of ownership
+async fn change_password(
+    user: AuthUser,
+    State(state): State<AppState>,
+    Json(request): Json<ChangePasswordRequest>,
+) -> Result<Json<Value>, AppError> {
+    if !is_strong_enough(&request.new_password) {
+        return Err(AppError::Validation(format!(
+            "Password must be at least {MIN_PASSWORD_LENGTH} characters"
+        )));
+    }
+
+    let repo = UserRepository::new(state.db);
+
+    if !repo.is_verified(user.id).await? {
+        return Err(AppError::Validation(
+            "Email must be verified before changing the account password".to_string(),
+        ));
+    }
+
+    let current_hash = repo
+        .find_password_hash_by_id(user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if !verify_password(&request.current_password, &current_hash)? {
+        return Err(AppError::Auth("Current password is incorrect".to_string()));
+    }
+
+    let new_hash = hash_password(&request.new_password)?;
+    repo.update_password_hash(user.id, &new_hash).await?;
+
+    Ok(Json(json!({
+        "message": "Password updated successfully"
+    })))
+}
+
+/// Re-issue an `EmailVerify` OTP for the caller's registered address (e.g. the one sent at
+/// signup expired or was lost), dispatching it through `state.email_sender` rather than
+/// returning it in the response.
+async fn request_email_verification(
+    user: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    let code = VerificationOtpRepository::new(state.db.clone())
+        .issue(user.id, VerificationPurpose::EmailVerify)
+        .await?;
+    state.email_sender.send_verification_code(&user.email, &code);
+
+    Ok(Json(json!({ "message": "Verification code sent" })))
+}
+
+/// Confirm a previously issued `EmailVerify` OTP, marking the account verified.
+async fn confirm_email_verification(
+    user: AuthUser,
+    State(state): State<AppState>,
+    Json(request): Json<ConfirmEmailVerificationRequest>,
+) -> Result<Json<Value>, AppError> {
+    let accepted = VerificationOtpRepository::new(state.db.clone())
+        .consume(user.id, VerificationPurpose::EmailVerify, &request.code)
+        .await?;
+
+    if !accepted {
+        return Err(AppError::Validation(
+            "Verification code is invalid or has expired".to_string(),
+        ));
+    }
+
+    UserRepository::new(state.db)
+        .set_verified(user.id, true)
+        .await?;
+
+    Ok(Json(json!({ "message": "Email verified successfully" })))
+}
+
+/// Begin 2FA enrollment: generate a new secret and recovery codes. The secret is stored
+/// sealed but not yet marked enabled until the user proves possession via `/2fa/verify`.
+async fn enable_totp(
+    user: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    let repo = UserRepository::new(state.db.clone());
+
+    let secret = totp::generate_secret();
+    let sealed = totp::seal_secret(&secret, &totp::encryption_key_from_env());
+    repo.set_totp_secret(user.id, &sealed).await?;
+
+    let recovery_codes = totp::generate_recovery_codes(RECOVERY_CODE_COUNT);
+    let recovery_code_hashes: Vec<String> = recovery_codes
+        .iter()
+        .map(|code| crate::auth::jwt::hash_refresh_token(code))
+        .collect();
+    TotpRecoveryCodeRepository::new(state.db)
+        .replace_all(user.id, &recovery_code_hashes)
+        .await?;
+
+    Ok(Json(json!({
+        "otpauth_uri": totp::otpauth_uri("off-kilter", &user.email, &secret),
+        "secret": totp::encode_secret(&secret),
+        "recovery_codes": recovery_codes
+    })))
+}
+
+/// Confirm enrollment by verifying a code generated from the pending secret, flipping
+/// `totp_enabled` to true.
+async fn verify_totp(
+    user: AuthUser,
+    State(state): State<AppState>,
+    Json(request): Json<VerifyTotpRequest>,
+) -> Result<Json<Value>, AppError> {
+    let repo = UserRepository::new(state.db);
+
+    let (sealed_secret, _enabled) = repo
+        .get_totp_state(user.id)
+        .await?
+        .ok_or_else(|| AppError::Validation("2FA has not been set up".to_string()))?;
+
+    let secret = totp::open_secret(&sealed_secret, &totp::encryption_key_from_env())
+        .ok_or(AppError::Internal)?;
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    if !totp::verify_code(&secret, &request.code, now) {
+        return Err(AppError::Auth("Invalid authentication code".to_string()));
+    }
+
+    repo.set_totp_enabled(user.id, true).await?;
+
+    Ok(Json(json!({ "message": "Two-factor authentication enabled" })))
+}
+
+/// Disable 2FA, requiring a valid code as proof the caller still controls the authenticator.
+async fn disable_totp(
+    user: AuthUser,
+    State(state): State<AppState>,
+    Json(request): Json<DisableTotpRequest>,
+) -> Result<Json<Value>, AppError> {
+    let repo = UserRepository::new(state.db.clone());
+
+    let (sealed_secret, enabled) = repo
+        .get_totp_state(user.id)
+        .await?
+        .ok_or_else(|| AppError::Validation("2FA is not enabled".to_string()))?;
+
+    if !enabled {
+        return Err(AppError::Validation("2FA is not enabled".to_string()));
+    }
+
+    let secret = totp::open_secret(&sealed_secret, &totp::encryption_key_from_env())
+        .ok_or(AppError::Internal)?;
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    if !totp::verify_code(&secret, &request.code, now) {
+        return Err(AppError::Auth("Invalid authentication code".to_string()));
+    }
+
+    repo.clear_totp(user.id).await?;
+    TotpRecoveryCodeRepository::new(state.db)
+        .delete_all(user.id)
+        .await?;
+
+    Ok(Json(json!({ "message": "Two-factor authentication disabled" })))
+}
+
 /// Get current user's profile
 async fn get_current_user(
     user: AuthUser,
@@ -118,16 +288,16 @@ async fn get_current_user(
     let profile_data = profile.get_profile_data()
         .map_err(|e| AppError::InternalError(format!("Failed to parse profile data: {}", e)))?;
 
-    let statistics_data = statistics.get_statistics_data()
+    // The caller is always the owner of their own profile, so nothing is redacted.
+    let public_stats = statistics
+        .view_as(
+            ViewerRelation::Owner,
+            &profile_data.privacy_settings.statistics_visibility,
+            &profile_data.privacy_settings.history_visibility,
+            profile_data.preferred_grading_system,
+        )
         .map_err(|e| AppError::InternalError(format!("Failed to parse statistics data: {}", e)))?;
 
-    // Respect privacy settings using helper function
-    let public_stats = create_public_statistics(
-        &statistics,
-        &statistics_data,
-        &profile_data.privacy_settings.statistics_visibility,
-    );
-
     let public_user = PublicUser {
         id: user_data.id,
         username: user_data.username,
@@ -192,26 +362,89 @@ async fn update_current_user(
     })))
 }
 
-/// Delete current user's account
+/// Soft-delete the current user's account, starting its retention window rather than
+/// removing the row immediately. Revokes every outstanding session so the account is unusable
+/// right away even though it isn't purged until retention elapses.
 async fn delete_current_user(
     user: AuthUser,
     State(state): State<AppState>,
 ) -> Result<Json<Value>, AppError> {
-    let repo = UserRepository::new(state.db);
+    crate::repositories::refresh_token::RefreshTokenRepository::new(state.db.clone())
+        .revoke_all_for_user(user.id)
+        .await?;
 
-    repo.delete_user(user.id).await?;
+    let repo = UserRepository::new(state.db);
+    repo.soft_delete_user(user.id).await?;
 
     Ok(Json(json!({
-        "message": "Account deleted successfully"
+        "message": "Account scheduled for deletion"
     })))
 }
 
-/// Get public user profile by ID
+/// Deactivate the current user's account (a reversible, user-initiated pause). Goes through
+/// `User::deactivate` rather than writing the status directly, so the transition rules (only
+/// valid from `Active`) are actually enforced on this path.
+async fn deactivate_current_user(
+    user: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    let repo = UserRepository::new(state.db);
+    let mut current = repo
+        .find_by_id(user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    current.deactivate()?;
+    repo.set_status(user.id, current.status).await?;
+
+    Ok(Json(json!({ "message": "Account deactivated" })))
+}
+
+/// Reactivate a deactivated (or admin-suspended) account back to active. Goes through
+/// `User::reactivate`, which rejects a `SoftDeleted` account — the retention-then-purge path
+/// is one-directional by design, so this can't be used to undo a self-deletion.
+async fn reactivate_current_user(
+    user: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    let repo = UserRepository::new(state.db);
+    let mut current = repo
+        .find_by_id(user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    current.reactivate()?;
+    repo.set_status(user.id, current.status).await?;
+
+    Ok(Json(json!({ "message": "Account reactivated" })))
+}
+
+/// Export the current user's data as a single portable JSON bundle, for a GDPR Article 20
+/// data-portability request.
+async fn export_current_user_data(
+    user: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    let repo = UserRepository::new(state.db);
+
+    let (user_data, profile, statistics) = repo.get_user_with_details(user.id).await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let export = user_data
+        .export_data(&profile, &statistics)
+        .map_err(AppError::Json)?;
+
+    Ok(Json(serde_json::from_str(&export)?))
+}
+
+/// Get public user profile by ID, projected according to the viewer's relationship to the
+/// owner (anonymous, stranger, confirmed friend, or the owner themselves).
 async fn get_user_by_id(
+    OptionalAuthUser(viewer): OptionalAuthUser,
     Path(user_id): Path<Uuid>,
     State(state): State<AppState>,
 ) -> Result<Json<PublicUser>, AppError> {
-    let repo = UserRepository::new(state.db);
+    let repo = UserRepository::new(state.db.clone());
 
     let (user_data, profile, statistics) = repo.get_user_with_details(user_id).await?
         .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
@@ -219,20 +452,24 @@ async fn get_user_by_id(
     let profile_data = profile.get_profile_data()
         .map_err(|e| AppError::InternalError(format!("Failed to parse profile data: {}", e)))?;
 
-    let statistics_data = statistics.get_statistics_data()
+    let relation = resolve_viewer_relation(viewer.as_ref(), user_id, &state).await?;
+
+    // Project the profile and statistics through the viewer's relationship tier.
+    let filtered_profile = profile_data.view_as(relation);
+    let public_stats = statistics
+        .view_as(
+            relation,
+            &profile_data.privacy_settings.statistics_visibility,
+            &profile_data.privacy_settings.history_visibility,
+            profile_data.preferred_grading_system,
+        )
         .map_err(|e| AppError::InternalError(format!("Failed to parse statistics data: {}", e)))?;
 
-    // Check privacy settings for public access using helper functions
-    let filtered_profile = filter_profile_for_public(&profile_data);
-    let public_stats = create_public_statistics(
-        &statistics,
-        &statistics_data,
-        &profile_data.privacy_settings.statistics_visibility,
-    );
-
     let public_user = PublicUser {
         id: user_data.id,
-        username: if profile_data.privacy_settings.profile_visibility == "private" {
+        username: if relation != ViewerRelation::Owner
+            && profile_data.privacy_settings.profile_visibility == "private"
+        {
             "Private User".to_string()
         } else {
             user_data.username
@@ -245,6 +482,83 @@ async fn get_user_by_id(
     Ok(Json(public_user))
 }
 
+/// Resolve the viewer's relationship to a profile owner: self, confirmed friend, or stranger.
+async fn resolve_viewer_relation(
+    viewer: Option<&AuthUser>,
+    owner_id: Uuid,
+    state: &AppState,
+) -> Result<ViewerRelation, AppError> {
+    let Some(viewer) = viewer else {
+        return Ok(ViewerRelation::Stranger);
+    };
+
+    if viewer.id == owner_id {
+        return Ok(ViewerRelation::Owner);
+    }
+
+    let are_friends = RelationshipRepository::new(state.db.clone())
+        .are_friends(viewer.id, owner_id)
+        .await?;
+
+    Ok(if are_friends {
+        ViewerRelation::Friend
+    } else {
+        ViewerRelation::Stranger
+    })
+}
+
+/// Send a friend request to another user
+async fn send_friend_request(
+    user: AuthUser,
+    Path(target_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    RelationshipRepository::new(state.db)
+        .send_request(user.id, target_id)
+        .await?;
+
+    Ok(Json(json!({ "message": "Friend request sent" })))
+}
+
+/// Accept a pending friend request that `requester_id` sent to the caller
+async fn accept_friend_request(
+    user: AuthUser,
+    Path(requester_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    RelationshipRepository::new(state.db)
+        .accept(requester_id, user.id)
+        .await?;
+
+    Ok(Json(json!({ "message": "Friend request accepted" })))
+}
+
+/// Reject a pending friend request that `requester_id` sent to the caller
+async fn reject_friend_request(
+    user: AuthUser,
+    Path(requester_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    RelationshipRepository::new(state.db)
+        .reject(requester_id, user.id)
+        .await?;
+
+    Ok(Json(json!({ "message": "Friend request rejected" })))
+}
+
+/// Remove an existing friend relationship in either direction
+async fn remove_friend(
+    user: AuthUser,
+    Path(other_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, AppError> {
+    RelationshipRepository::new(state.db)
+        .remove(user.id, other_id)
+        .await?;
+
+    Ok(Json(json!({ "message": "Friend removed" })))
+}
+
 #[cfg(test)]
 mod tests {
     // Note: These tests would require a test database setup
@@ -2,7 +2,11 @@ pub mod models;
 pub mod handlers;
 pub mod database;
 pub mod auth;
+pub mod csrf;
+pub mod email;
 pub mod error;
 pub mod config;
+pub mod logging;
+pub mod media;
 pub mod repositories;
 pub mod state;
\ No newline at end of file
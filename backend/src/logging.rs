@@ -0,0 +1,66 @@
+//! Structured logging setup. `LogFormat` selects the `tracing-subscriber` formatter from
+//! `LOG_FORMAT` (`pretty` for local development, `compact` or `json` for deployments that ship
+//! logs to a collector), and `init` wires that formatter to an `EnvFilter` driven by
+//! `AppConfig::log_level`. Per-request spans (method, path, a generated request id, status,
+//! latency) are attached in `main` via `tower_http::trace::TraceLayer`; anything logged while
+//! handling a request nests under that span automatically.
+
+use std::{env, fmt, str::FromStr};
+
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::config::AppConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Compact,
+    Json,
+}
+
+impl LogFormat {
+    pub fn from_env() -> Self {
+        env::var("LOG_FORMAT")
+            .ok()
+            .and_then(|raw| raw.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = ParseLogFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(LogFormat::Pretty),
+            "compact" => Ok(LogFormat::Compact),
+            "json" => Ok(LogFormat::Json),
+            other => Err(ParseLogFormatError(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseLogFormatError(String);
+
+impl fmt::Display for ParseLogFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unrecognized LOG_FORMAT {:?}, expected pretty/compact/json", self.0)
+    }
+}
+
+impl std::error::Error for ParseLogFormatError {}
+
+/// Install the global `tracing` subscriber. Must run once, before the first `info!`/`error!`
+/// call that should be captured; see `main`, which calls this right after loading `config`.
+pub fn init(config: &AppConfig) {
+    let filter = EnvFilter::try_new(&config.log_level).unwrap_or_else(|_| EnvFilter::new("info"));
+    let registry = tracing_subscriber::registry().with(filter);
+
+    match config.log_format {
+        LogFormat::Pretty => registry.with(tracing_subscriber::fmt::layer().pretty()).init(),
+        LogFormat::Compact => registry.with(tracing_subscriber::fmt::layer().compact()).init(),
+        LogFormat::Json => registry.with(tracing_subscriber::fmt::layer().json()).init(),
+    }
+}
@@ -1,42 +1,56 @@
 use axum::{
-    extract::State,
+    extract::{Request, State},
     http::StatusCode,
     response::Json,
     routing::get,
     Router,
 };
 use serde_json::{json, Value};
-use tower_http::cors::CorsLayer;
-use tracing::{error, info};
-use tracing_subscriber;
+use std::time::Duration;
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use tracing::{error, error_span, info, Span};
+use uuid::Uuid;
 
 use kilter_board_backend::{
     config::AppConfig,
+    csrf::csrf_middleware,
     database::connection::{create_pool, run_migrations, health_check},
-    auth::{AuthUser, RequireAuth},
-    handlers::user::user_routes,
-    state::AppState,
+    auth::{AuthUser, OAuthConfig, RequireAuth, VerifiedUser},
+    email::LoggingEmailSender,
+    handlers::{
+        admin::admin_routes, auth::auth_routes, avatar::avatar_routes,
+        federation::federation_routes, oauth::oauth_routes, user::user_routes,
+    },
+    logging,
+    state::{AppState, AuthMetrics, BlockedUserCache},
 };
+use std::sync::Arc;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize tracing
-    tracing_subscriber::fmt::init();
-
     // Load configuration
     let config = AppConfig::from_env()?;
+
+    // Initialize tracing (format/level driven by `config`, see `logging::init`)
+    logging::init(&config);
     info!("Starting Kilter Board API server");
 
     // Create database connection pool
     let db_pool = create_pool(&config.database).await?;
-    
+
     // Run database migrations
     run_migrations(&db_pool).await?;
+    health_check(&db_pool).await?;
 
     // Create application state
-    let app_state = AppState { 
+    let app_state = AppState {
         db: db_pool,
         jwt_config: config.jwt.clone(),
+        blocked_users: Arc::new(BlockedUserCache::default()),
+        auth_metrics: Arc::new(AuthMetrics::default()),
+        federation_base_url: config.federation_base_url.clone(),
+        oauth_config: OAuthConfig::from_env().ok().map(Arc::new),
+        email_sender: Arc::new(LoggingEmailSender),
     };
 
     // Build our application with routes
@@ -44,10 +58,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/", get(root))
         .route("/health", get(health))
         .route("/protected", get(protected_route))
+        .route("/protected/verified", get(verified_only_route))
         .route("/user-info", get(user_info))
         .merge(user_routes())
+        .merge(auth_routes())
+        .merge(avatar_routes())
+        .merge(admin_routes())
+        .merge(federation_routes())
+        .merge(oauth_routes())
         .with_state(app_state)
-        .layer(CorsLayer::permissive());
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(config.csrf.clone()),
+            csrf_middleware,
+        ))
+        .layer(CorsLayer::permissive())
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(|req: &Request| {
+                    error_span!(
+                        "http_request",
+                        method = %req.method(),
+                        path = %req.uri().path(),
+                        request_id = %Uuid::new_v4(),
+                        status = tracing::field::Empty,
+                        latency_ms = tracing::field::Empty,
+                    )
+                })
+                .on_response(|response: &axum::response::Response, latency: Duration, span: &Span| {
+                    span.record("status", response.status().as_u16());
+                    span.record("latency_ms", latency.as_millis() as u64);
+                }),
+        );
 
     // Start the server
     let listener = tokio::net::TcpListener::bind(&config.server_address()).await?;
@@ -67,7 +108,8 @@ async fn health(State(state): State<AppState>) -> Result<Json<Value>, StatusCode
         Ok(_) => Ok(Json(json!({
             "status": "healthy",
             "database": "connected",
-            "timestamp": chrono::Utc::now().to_rfc3339()
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "auth": state.auth_metrics.snapshot()
         }))),
         Err(e) => {
             error!("Database health check failed: {}", e);
@@ -84,6 +126,15 @@ async fn protected_route(_auth: RequireAuth) -> Json<Value> {
     }))
 }
 
+// Test endpoint demonstrating a verified-only gated route: an unverified account can still
+// authenticate (see `user_info` below) but is rejected here until it confirms its email.
+async fn verified_only_route(_user: VerifiedUser) -> Json<Value> {
+    Json(json!({
+        "message": "This route requires a verified account",
+        "authenticated": true
+    }))
+}
+
 // Test endpoint that extracts user information from JWT
 async fn user_info(user: AuthUser) -> Json<Value> {
     Json(json!({
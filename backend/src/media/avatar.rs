@@ -0,0 +1,111 @@
+//! Server-side avatar processing: validate, normalize, and resize user-uploaded images so
+//! `avatar_url` always points at content we generated ourselves rather than a client-supplied
+//! URL (which would otherwise be an SSRF/hotlinking vector).
+
+use image::{imageops::FilterType, ImageFormat};
+use std::io::Cursor;
+use thiserror::Error;
+
+/// Hard cap on upload size, checked before any decoding is attempted.
+pub const MAX_UPLOAD_BYTES: usize = 5 * 1024 * 1024;
+
+/// Square thumbnail sizes generated for every avatar, largest first.
+pub const AVATAR_SIZES: [u32; 2] = [256, 64];
+
+#[derive(Debug, Error)]
+pub enum AvatarError {
+    #[error("image exceeds the {MAX_UPLOAD_BYTES}-byte size cap")]
+    TooLarge,
+    #[error("unrecognized or unsupported image format")]
+    UnsupportedFormat,
+    #[error("failed to decode image")]
+    DecodeFailed,
+    #[error("failed to encode resized image")]
+    EncodeFailed,
+}
+
+/// One generated thumbnail: its square size in pixels and PNG-encoded bytes.
+pub struct AvatarThumbnail {
+    pub size: u32,
+    pub png_bytes: Vec<u8>,
+}
+
+/// Sniff the real image format from magic bytes, ignoring any client-supplied content type.
+fn sniff_format(bytes: &[u8]) -> Option<ImageFormat> {
+    image::guess_format(bytes).ok()
+}
+
+/// Validate, decode, strip metadata (EXIF et al, dropped implicitly by re-encoding through
+/// `image`), crop to a centered square, and resize to each of [`AVATAR_SIZES`].
+pub fn process_avatar(bytes: &[u8]) -> Result<Vec<AvatarThumbnail>, AvatarError> {
+    if bytes.len() > MAX_UPLOAD_BYTES {
+        return Err(AvatarError::TooLarge);
+    }
+
+    let format = sniff_format(bytes).ok_or(AvatarError::UnsupportedFormat)?;
+    if !matches!(format, ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::WebP) {
+        return Err(AvatarError::UnsupportedFormat);
+    }
+
+    let image = image::load_from_memory_with_format(bytes, format)
+        .map_err(|_| AvatarError::DecodeFailed)?;
+
+    let square = crop_to_square(image);
+
+    AVATAR_SIZES
+        .iter()
+        .map(|&size| {
+            let resized = square.resize_exact(size, size, FilterType::Lanczos3);
+            let mut png_bytes = Vec::new();
+            resized
+                .write_to(&mut Cursor::new(&mut png_bytes), ImageFormat::Png)
+                .map_err(|_| AvatarError::EncodeFailed)?;
+            Ok(AvatarThumbnail { size, png_bytes })
+        })
+        .collect()
+}
+
+/// Crop the larger dimension down so the image is square, centering the crop.
+fn crop_to_square(image: image::DynamicImage) -> image::DynamicImage {
+    let (width, height) = (image.width(), image.height());
+    let side = width.min(height);
+    let x = (width - side) / 2;
+    let y = (height - side) / 2;
+    image.crop_imm(x, y, side, side)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiny_png() -> Vec<u8> {
+        let image = image::DynamicImage::new_rgb8(300, 200);
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_process_avatar_produces_configured_sizes() {
+        let thumbnails = process_avatar(&tiny_png()).unwrap();
+        let sizes: Vec<u32> = thumbnails.iter().map(|t| t.size).collect();
+        assert_eq!(sizes, AVATAR_SIZES.to_vec());
+    }
+
+    #[test]
+    fn test_process_avatar_rejects_oversized_input() {
+        let oversized = vec![0u8; MAX_UPLOAD_BYTES + 1];
+        assert!(matches!(process_avatar(&oversized), Err(AvatarError::TooLarge)));
+    }
+
+    #[test]
+    fn test_process_avatar_rejects_non_image_bytes() {
+        let not_an_image = b"just some text, not an image".to_vec();
+        assert!(matches!(
+            process_avatar(&not_an_image),
+            Err(AvatarError::UnsupportedFormat)
+        ));
+    }
+}
@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use thiserror::Error;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
+use crate::models::grading::{Grade, GradeError};
+
 /// Represents the state and type of a hold on the Kilter board
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum HoldState {
@@ -28,6 +31,76 @@ pub enum HoldType {
     Finish,
 }
 
+/// How serious a [`Diagnostic`] is. Only `Error` fails [`HoldConfiguration::validate`]; a
+/// `Warning` is surfaced to an editor UI but doesn't block saving.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A structured, directly-applicable edit suggested alongside a [`Diagnostic`], plus a
+/// human-readable label an editor UI can show on a "quick fix" button.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum HoldConfigFix {
+    /// Clear a hold back to `NotUsed`.
+    RemoveHold { hold_id: String, label: String },
+    /// Change a hold to a different `HoldType` without removing it.
+    RetypeHold {
+        hold_id: String,
+        to: HoldType,
+        label: String,
+    },
+}
+
+/// One issue found by a [`HoldConfiguration`] lint rule. Modeled on the rule/diagnostic/fixer
+/// pattern of linters like rslint: each rule function pushes zero or more of these, so a caller
+/// sees every problem at once instead of just the first one `validate` would have stopped at.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable machine-readable identifier for the rule that raised this, e.g.
+    /// `"too-many-start-holds"` — for clients that want to filter or localize by rule rather
+    /// than matching on `message`.
+    pub code: &'static str,
+    pub message: String,
+    pub fix: Option<HoldConfigFix>,
+}
+
+/// Physical position of a single hold on the board, independent of what any particular problem
+/// does with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldPosition {
+    pub x: f32,
+    pub y: f32,
+    /// The `hold_id` of this hold's left/right mirror counterpart, if the board layout is
+    /// symmetric at this position. `None` for holds on the centerline, or boards without a
+    /// defined mirror layout.
+    pub mirror_of: Option<String>,
+}
+
+/// A board's physical hold layout: where every `hold_id` sits, and its mirror counterpart if
+/// any. Loaded once per board model (e.g. from a layout definition file) and shared across all
+/// of that board's problems, which otherwise only deal in opaque `hold_id` strings.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BoardLayout {
+    pub positions: HashMap<String, HoldPosition>,
+}
+
+impl BoardLayout {
+    pub fn new() -> Self {
+        Self {
+            positions: HashMap::new(),
+        }
+    }
+
+    pub fn position(&self, hold_id: &str) -> Option<&HoldPosition> {
+        self.positions.get(hold_id)
+    }
+}
+
 /// Configuration of all holds on the board for a specific problem
 /// This represents the "matrix" of hold states across the entire board
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,15 +116,96 @@ pub struct BoulderProblem {
     pub id: Uuid,
     pub name: String,
     pub creator_id: Uuid,
-    pub difficulty: String, // V-scale grade (V0, V1, V2, etc.)
+    pub difficulty: String, // Grade label in any system Grade::parse understands; kept as a bare String for DB compatibility
     pub hold_configuration: serde_json::Value, // Stored as JSONB in database
     pub tags: Vec<String>,
     pub ascent_count: i32,
     pub is_published: bool,
+    /// The adjustable wall angle this problem was set at, in degrees (typical Kilter range
+    /// 0-70). `None` when the angle wasn't recorded, e.g. for problems imported before this
+    /// field existed. The same holds can grade very differently at different angles, so this
+    /// is tracked alongside `difficulty` rather than inferred from it.
+    pub angle_degrees: Option<i16>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// A grade paired with the wall angle it was set at. The same [`HoldConfiguration`] yields a
+/// different grade depending on angle, so neither is a stable identity without the other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradedProblem {
+    pub difficulty: String,
+    pub angle_degrees: Option<i16>,
+}
+
+impl GradedProblem {
+    pub fn new(difficulty: String, angle_degrees: Option<i16>) -> Self {
+        Self {
+            difficulty,
+            angle_degrees,
+        }
+    }
+
+    /// Parse `difficulty` into a canonical [`Grade`], independent of which scale it was
+    /// recorded in.
+    pub fn grade(&self) -> Result<Grade, GradeError> {
+        Grade::parse(&self.difficulty)
+    }
+}
+
+/// Maps Kilter Board role ids to [`HoldType`]s for [`HoldConfiguration::to_frames`] /
+/// [`HoldConfiguration::from_frames`]. The official app's role ids ([`RoleMap::default`]) are
+/// stable, but this is kept overridable rather than hardcoded in case a board revision or fork
+/// reassigns them.
+#[derive(Debug, Clone)]
+pub struct RoleMap {
+    start: u32,
+    hand: u32,
+    finish: u32,
+    foot: u32,
+}
+
+impl Default for RoleMap {
+    fn default() -> Self {
+        Self {
+            start: 12,
+            hand: 13,
+            finish: 14,
+            foot: 15,
+        }
+    }
+}
+
+impl RoleMap {
+    fn role_id_for(&self, hold_type: &HoldType) -> u32 {
+        match hold_type {
+            HoldType::Start => self.start,
+            HoldType::Hand => self.hand,
+            HoldType::Finish => self.finish,
+            HoldType::Foot => self.foot,
+        }
+    }
+
+    fn hold_type_for(&self, role_id: u32) -> Option<HoldType> {
+        match role_id {
+            id if id == self.start => Some(HoldType::Start),
+            id if id == self.hand => Some(HoldType::Hand),
+            id if id == self.finish => Some(HoldType::Finish),
+            id if id == self.foot => Some(HoldType::Foot),
+            _ => None,
+        }
+    }
+}
+
+/// Error from [`HoldConfiguration::from_frames`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FramesParseError {
+    #[error("malformed frames token {0:?}, expected p<digits>r<digits>")]
+    MalformedToken(String),
+    #[error("unknown role id {0} in frames string")]
+    UnknownRole(u32),
+}
+
 /// Request/Response DTOs for API endpoints
 
 /// Request to create a new boulder problem
@@ -59,11 +213,39 @@ pub struct BoulderProblem {
 pub struct CreateBoulderProblemRequest {
     pub name: String,
     pub difficulty: String,
-    pub hold_configuration: HoldConfiguration,
+    pub hold_configuration: Option<HoldConfiguration>,
+    /// Alternative to `hold_configuration`: the Kilter Board app's compact placement string
+    /// (e.g. `p1183r12p1184r13p1185r14`), parsed via `HoldConfiguration::from_frames`. Exactly
+    /// one of `hold_configuration`/`frames` must be set.
+    pub frames: Option<String>,
     pub tags: Option<Vec<String>>,
     pub is_published: Option<bool>,
 }
 
+/// Error from [`CreateBoulderProblemRequest::resolve_hold_configuration`].
+#[derive(Debug, Error)]
+pub enum CreateProblemConfigError {
+    #[error("either hold_configuration or frames must be provided")]
+    Missing,
+    #[error("only one of hold_configuration or frames may be provided")]
+    BothProvided,
+    #[error("invalid frames string: {0}")]
+    InvalidFrames(#[from] FramesParseError),
+}
+
+impl CreateBoulderProblemRequest {
+    /// Resolve whichever of `hold_configuration`/`frames` was provided into a structured
+    /// `HoldConfiguration`.
+    pub fn resolve_hold_configuration(&self) -> Result<HoldConfiguration, CreateProblemConfigError> {
+        match (&self.hold_configuration, &self.frames) {
+            (Some(config), None) => Ok(config.clone()),
+            (None, Some(frames)) => Ok(HoldConfiguration::from_frames(frames)?),
+            (None, None) => Err(CreateProblemConfigError::Missing),
+            (Some(_), Some(_)) => Err(CreateProblemConfigError::BothProvided),
+        }
+    }
+}
+
 /// Request to update an existing boulder problem
 #[derive(Debug, Deserialize)]
 pub struct UpdateBoulderProblemRequest {
@@ -141,34 +323,179 @@ impl HoldConfiguration {
             .collect()
     }
 
-    /// Validate the hold configuration according to Kilter board rules
+    /// Validate the hold configuration according to Kilter board rules, without a claimed
+    /// grade to check reachability against. Equivalent to `validate_for_grade(None)`.
     pub fn validate(&self) -> Result<(), String> {
-        let start_holds = self.get_holds_by_type(HoldType::Start);
-        let finish_holds = self.get_holds_by_type(HoldType::Finish);
+        self.validate_for_grade(None)
+    }
+
+    /// Like [`validate`](Self::validate), but also runs grade-aware rules (e.g. an
+    /// implausibly sparse hold set for an easy grade) against `grade` when given. Returns
+    /// `Err` if any [`Severity::Error`] diagnostic was raised; warnings don't fail validation.
+    pub fn validate_for_grade(&self, grade: Option<&str>) -> Result<(), String> {
+        self.validate_with_layout(grade, None, DEFAULT_MAX_REACH)
+    }
+
+    /// Like [`validate_for_grade`](Self::validate_for_grade), additionally running the
+    /// physical-reach rule against `layout` when given. `max_reach` is in the same units as
+    /// [`HoldPosition`]'s coordinates; pass [`DEFAULT_MAX_REACH`] absent a board-specific value.
+    pub fn validate_with_layout(
+        &self,
+        grade: Option<&str>,
+        layout: Option<&BoardLayout>,
+        max_reach: f32,
+    ) -> Result<(), String> {
+        let errors: Vec<String> = self
+            .diagnostics_with_layout(grade, layout, max_reach)
+            .into_iter()
+            .filter(|d| d.severity == Severity::Error)
+            .map(|d| d.message)
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
 
-        // Check start holds constraint (1-2 start holds)
-        if start_holds.is_empty() {
-            return Err("Problem must have at least 1 start hold".to_string());
+    /// Run every lint rule and collect all issues found, instead of stopping at the first
+    /// problem — so an editor UI can surface everything wrong with a configuration at once.
+    /// `grade` enables the grade-reachability rule; pass `None` to skip it. Equivalent to
+    /// `diagnostics_with_layout(grade, None, DEFAULT_MAX_REACH)`.
+    pub fn diagnostics(&self, grade: Option<&str>) -> Vec<Diagnostic> {
+        self.diagnostics_with_layout(grade, None, DEFAULT_MAX_REACH)
+    }
+
+    /// Like [`diagnostics`](Self::diagnostics), additionally running the physical-reach rule
+    /// against `layout` when given.
+    pub fn diagnostics_with_layout(
+        &self,
+        grade: Option<&str>,
+        layout: Option<&BoardLayout>,
+        max_reach: f32,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        check_start_holds(self, &mut diagnostics);
+        check_finish_holds(self, &mut diagnostics);
+        check_minimum_holds(self, &mut diagnostics);
+        check_foot_holds(self, &mut diagnostics);
+        check_dual_role_holds(self, &mut diagnostics);
+        check_grade_reachability(self, grade, &mut diagnostics);
+        if let Some(layout) = layout {
+            check_reach(self, layout, max_reach, &mut diagnostics);
         }
-        if start_holds.len() > 2 {
-            return Err("Problem cannot have more than 2 start holds".to_string());
+        diagnostics
+    }
+
+    /// Flip the configuration left/right using the board's mirror-symmetric hold pairs, e.g. so
+    /// a creator can offer the same problem in both orientations. Holds with no `mirror_of`
+    /// entry in `layout` (including holds `layout` doesn't know about at all) stay put.
+    pub fn mirror(&self, layout: &BoardLayout) -> HoldConfiguration {
+        let mut mirrored = HoldConfiguration::new();
+        for (hold_id, state) in &self.holds {
+            if let HoldState::Used(hold_type) = state {
+                let target_id = layout
+                    .position(hold_id)
+                    .and_then(|pos| pos.mirror_of.clone())
+                    .unwrap_or_else(|| hold_id.clone());
+                mirrored.add_hold(target_id, HoldState::Used(hold_type.clone()));
+            }
         }
+        mirrored
+    }
 
-        // Check finish holds constraint (1-2 finish holds)
-        if finish_holds.is_empty() {
-            return Err("Problem must have at least 1 finish hold".to_string());
+    /// The axis-aligned `(min_x, min_y, max_x, max_y)` box enclosing every used hold that
+    /// `layout` has a position for. `None` if none of the used holds appear in `layout`.
+    pub fn bounding_box(&self, layout: &BoardLayout) -> Option<(f32, f32, f32, f32)> {
+        let mut positions = self.holds.keys().filter_map(|id| layout.position(id));
+        let first = positions.next()?;
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (first.x, first.y, first.x, first.y);
+
+        for pos in positions {
+            min_x = min_x.min(pos.x);
+            min_y = min_y.min(pos.y);
+            max_x = max_x.max(pos.x);
+            max_y = max_y.max(pos.y);
         }
-        if finish_holds.len() > 2 {
-            return Err("Problem cannot have more than 2 finish holds".to_string());
+
+        Some((min_x, min_y, max_x, max_y))
+    }
+
+    /// The `(width, height)` of [`bounding_box`](Self::bounding_box), for estimating a
+    /// problem's overall reach/height requirements.
+    pub fn span(&self, layout: &BoardLayout) -> Option<(f32, f32)> {
+        let (min_x, min_y, max_x, max_y) = self.bounding_box(layout)?;
+        Some((max_x - min_x, max_y - min_y))
+    }
+
+    /// Apply a structured fix suggested by a [`Diagnostic`], e.g. one returned by
+    /// [`diagnostics`](Self::diagnostics). Does not re-validate afterward — callers that want
+    /// to confirm the fix resolved the issue should call `validate`/`diagnostics` again.
+    pub fn apply_fix(&mut self, fix: HoldConfigFix) {
+        match fix {
+            HoldConfigFix::RemoveHold { hold_id, .. } => {
+                self.holds.remove(&hold_id);
+            }
+            HoldConfigFix::RetypeHold { hold_id, to, .. } => {
+                self.holds.insert(hold_id, HoldState::Used(to));
+            }
         }
+    }
+
+    /// Serialize to the Kilter Board app's compact wire format using the default `RoleMap`:
+    /// `p<placement_id>r<role_id>` tokens concatenated with no separator, e.g.
+    /// `p1183r12p1184r13p1185r14`. Tokens are emitted sorted by numeric placement id so the
+    /// same configuration always round-trips to the same string. Holds whose `hold_id` isn't
+    /// numeric (i.e. didn't come from the real board) are silently skipped.
+    pub fn to_frames(&self) -> String {
+        self.to_frames_with_roles(&RoleMap::default())
+    }
+
+    /// Like [`to_frames`](Self::to_frames), with a non-default role-id table.
+    pub fn to_frames_with_roles(&self, roles: &RoleMap) -> String {
+        let mut placements: Vec<(u32, u32)> = self
+            .holds
+            .iter()
+            .filter_map(|(hold_id, state)| match state {
+                HoldState::Used(hold_type) => hold_id
+                    .parse::<u32>()
+                    .ok()
+                    .map(|placement_id| (placement_id, roles.role_id_for(hold_type))),
+                HoldState::NotUsed => None,
+            })
+            .collect();
+        placements.sort_by_key(|(placement_id, _)| *placement_id);
+
+        placements
+            .into_iter()
+            .map(|(placement_id, role_id)| format!("p{placement_id}r{role_id}"))
+            .collect()
+    }
+
+    /// Parse the Kilter Board app's compact placement string (see
+    /// [`to_frames`](Self::to_frames)) using the default `RoleMap`.
+    pub fn from_frames(s: &str) -> Result<Self, FramesParseError> {
+        Self::from_frames_with_roles(s, &RoleMap::default())
+    }
 
-        // Ensure we have at least some holds to climb on
-        let total_used_holds = self.holds.len();
-        if total_used_holds < 2 {
-            return Err("Problem must have at least 2 holds (start and finish)".to_string());
+    /// Like [`from_frames`](Self::from_frames), with a non-default role-id table.
+    pub fn from_frames_with_roles(s: &str, roles: &RoleMap) -> Result<Self, FramesParseError> {
+        let mut config = Self::new();
+        let mut rest = s;
+
+        while !rest.is_empty() {
+            let token_end = rest[1..].find('p').map_or(rest.len(), |i| i + 1);
+            let (token, remainder) = rest.split_at(token_end);
+            let (placement_id, role_id) = parse_frame_token(token)?;
+            let hold_type = roles
+                .hold_type_for(role_id)
+                .ok_or(FramesParseError::UnknownRole(role_id))?;
+            config.add_hold(placement_id.to_string(), HoldState::Used(hold_type));
+            rest = remainder;
         }
 
-        Ok(())
+        Ok(config)
     }
 
     /// Get a summary of hold types for display
@@ -199,17 +526,223 @@ pub struct HoldSummary {
     pub finish_holds: i32,
 }
 
+/// Default max reach distance between a hold and its nearest neighbor, in [`HoldPosition`]'s
+/// coordinate units, beyond which [`HoldConfiguration::diagnostics_with_layout`] warns that a
+/// hold may be unreachably far from the rest of the problem.
+pub const DEFAULT_MAX_REACH: f32 = 1.2;
+
+/// Split a single `p<digits>r<digits>` token into its placement and role ids.
+fn parse_frame_token(token: &str) -> Result<(u32, u32), FramesParseError> {
+    let malformed = || FramesParseError::MalformedToken(token.to_string());
+
+    let rest = token.strip_prefix('p').ok_or_else(malformed)?;
+    let r_pos = rest.find('r').ok_or_else(malformed)?;
+    let (placement_str, role_str) = (&rest[..r_pos], &rest[r_pos + 1..]);
+    if placement_str.is_empty() || role_str.is_empty() {
+        return Err(malformed());
+    }
+
+    let placement_id = placement_str.parse::<u32>().map_err(|_| malformed())?;
+    let role_id = role_str.parse::<u32>().map_err(|_| malformed())?;
+    Ok((placement_id, role_id))
+}
+
+/// Lint rules for [`HoldConfiguration::diagnostics`]. Each takes the configuration and pushes
+/// zero or more issues onto `diagnostics`; none of them short-circuit the others.
+
+fn check_start_holds(config: &HoldConfiguration, diagnostics: &mut Vec<Diagnostic>) {
+    let start_holds = config.get_holds_by_type(HoldType::Start);
+    if start_holds.is_empty() {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            code: "no-start-hold",
+            message: "Problem must have at least 1 start hold".to_string(),
+            fix: None,
+        });
+    } else if start_holds.len() > 2 {
+        for hold_id in &start_holds[2..] {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                code: "too-many-start-holds",
+                message: "Problem cannot have more than 2 start holds".to_string(),
+                fix: Some(HoldConfigFix::RemoveHold {
+                    hold_id: hold_id.clone(),
+                    label: format!("Remove start hold {hold_id}"),
+                }),
+            });
+        }
+    }
+}
+
+fn check_finish_holds(config: &HoldConfiguration, diagnostics: &mut Vec<Diagnostic>) {
+    let finish_holds = config.get_holds_by_type(HoldType::Finish);
+    if finish_holds.is_empty() {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            code: "no-finish-hold",
+            message: "Problem must have at least 1 finish hold".to_string(),
+            fix: None,
+        });
+    } else if finish_holds.len() > 2 {
+        for hold_id in &finish_holds[2..] {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                code: "too-many-finish-holds",
+                message: "Problem cannot have more than 2 finish holds".to_string(),
+                fix: Some(HoldConfigFix::RemoveHold {
+                    hold_id: hold_id.clone(),
+                    label: format!("Remove finish hold {hold_id}"),
+                }),
+            });
+        }
+    }
+}
+
+fn check_minimum_holds(config: &HoldConfiguration, diagnostics: &mut Vec<Diagnostic>) {
+    if config.holds.len() < 2 {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            code: "too-few-holds",
+            message: "Problem must have at least 2 holds (start and finish)".to_string(),
+            fix: None,
+        });
+    }
+}
+
+fn check_foot_holds(config: &HoldConfiguration, diagnostics: &mut Vec<Diagnostic>) {
+    if config.get_holds_by_type(HoldType::Foot).is_empty() {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            code: "no-foot-holds",
+            message: "Problem has no foot holds; climbers will have to smear or foot on hand holds".to_string(),
+            fix: None,
+        });
+    }
+}
+
+/// Flags a hold_id that is both a start and a finish hold. Not reachable through
+/// `HoldConfiguration::add_hold` today since a hold_id only ever maps to one `HoldState`, but
+/// kept as a rule in case that invariant loosens (e.g. a future multi-role hold).
+fn check_dual_role_holds(config: &HoldConfiguration, diagnostics: &mut Vec<Diagnostic>) {
+    let start_holds: std::collections::HashSet<String> =
+        config.get_holds_by_type(HoldType::Start).into_iter().collect();
+
+    for hold_id in config.get_holds_by_type(HoldType::Finish) {
+        if start_holds.contains(&hold_id) {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                code: "dual-role-hold",
+                message: format!("Hold {hold_id} is marked as both a start and a finish hold"),
+                fix: Some(HoldConfigFix::RetypeHold {
+                    hold_id: hold_id.clone(),
+                    to: HoldType::Hand,
+                    label: format!("Retype {hold_id} to a hand hold"),
+                }),
+            });
+        }
+    }
+}
+
+/// Warns when the total hold count looks implausibly sparse for the claimed grade — easier
+/// problems are generally set with more usable holds than hard ones. A no-op if `grade` isn't
+/// a recognized V-scale grade.
+fn check_grade_reachability(
+    config: &HoldConfiguration,
+    grade: Option<&str>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some(grade_num) = grade.and_then(|g| g.strip_prefix('V')).and_then(|n| n.parse::<i32>().ok()) else {
+        return;
+    };
+
+    let min_expected_holds = match grade_num {
+        0..=2 => 6,
+        3..=5 => 4,
+        _ => 2,
+    };
+
+    let used_holds = config.holds.len();
+    if used_holds < min_expected_holds {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            code: "unreachable-for-grade",
+            message: format!(
+                "Only {used_holds} hold(s) are used, which is sparse for a claimed grade of V{grade_num}"
+            ),
+            fix: None,
+        });
+    }
+}
+
+/// Error from [`BoulderProblem::new`].
+#[derive(Debug, Error)]
+pub enum BoulderProblemError {
+    #[error("invalid grade: {0}")]
+    InvalidGrade(#[from] GradeError),
+    #[error("invalid hold configuration: {0}")]
+    InvalidHoldConfiguration(String),
+    #[error("failed to serialize hold configuration: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+fn hold_position_distance(a: &HoldPosition, b: &HoldPosition) -> f32 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+/// Warns about a used hold sitting farther than `max_reach` from every other used hold —
+/// covering both an isolated start hold and an isolated hold anywhere else in the sequence.
+/// Holds `layout` has no position for are skipped rather than treated as unreachable, since not
+/// every configuration (e.g. ones built from hand-picked test ids) has full position data.
+fn check_reach(
+    config: &HoldConfiguration,
+    layout: &BoardLayout,
+    max_reach: f32,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let positions: Vec<(&String, &HoldPosition)> = config
+        .holds
+        .keys()
+        .filter_map(|hold_id| layout.position(hold_id).map(|pos| (hold_id, pos)))
+        .collect();
+
+    for (i, (hold_id, pos)) in positions.iter().enumerate() {
+        let nearest = positions
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, (_, other))| hold_position_distance(pos, other))
+            .fold(f32::INFINITY, f32::min);
+
+        if nearest.is_finite() && nearest > max_reach {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                code: "unreachable-gap",
+                message: format!(
+                    "Hold {hold_id} is {nearest:.2} units from its nearest neighbor, over the {max_reach:.2} max reach"
+                ),
+                fix: None,
+            });
+        }
+    }
+}
+
 impl BoulderProblem {
-    /// Create a new boulder problem
+    /// Create a new boulder problem. `difficulty` is validated through [`Grade::parse`] (any
+    /// scale `Grade` understands), so junk grades are rejected up front instead of silently
+    /// accepted. An accepted grade is stored exactly as given; it is not rewritten into a
+    /// canonical label.
     pub fn new(
         name: String,
         creator_id: Uuid,
         difficulty: String,
         hold_configuration: HoldConfiguration,
         tags: Vec<String>,
-    ) -> Result<Self, String> {
-        // Validate the hold configuration
-        hold_configuration.validate()?;
+        angle_degrees: Option<i16>,
+    ) -> Result<Self, BoulderProblemError> {
+        let grade = Grade::parse(&difficulty)?;
+        hold_configuration
+            .validate_for_grade(Some(&grade.to_v_scale()))
+            .map_err(BoulderProblemError::InvalidHoldConfiguration)?;
 
         let now = Utc::now();
         Ok(Self {
@@ -218,10 +751,11 @@ impl BoulderProblem {
             creator_id,
             difficulty,
             hold_configuration: serde_json::to_value(hold_configuration)
-                .map_err(|e| format!("Failed to serialize hold configuration: {}", e))?,
+                .map_err(BoulderProblemError::Serialization)?,
             tags,
             ascent_count: 0,
             is_published: false,
+            angle_degrees,
             created_at: now,
             updated_at: now,
         })
@@ -234,8 +768,11 @@ impl BoulderProblem {
 
     /// Update the hold configuration
     pub fn update_hold_configuration(&mut self, config: HoldConfiguration) -> Result<(), String> {
-        // Validate the new configuration
-        config.validate()?;
+        // Validate the new configuration, including grade-reachability against this problem's
+        // difficulty (normalized to V-scale, since this problem's `difficulty` may have been
+        // recorded in any scale `Grade` understands).
+        let grade_hint = Grade::parse(&self.difficulty).ok().map(|g| g.to_v_scale());
+        config.validate_for_grade(grade_hint.as_deref())?;
         
         self.hold_configuration = serde_json::to_value(config)
             .map_err(|e| format!("Failed to serialize hold configuration: {}", e))?;
@@ -357,18 +894,66 @@ mod tests {
             "V3".to_string(),
             config,
             vec!["overhang".to_string(), "crimpy".to_string()],
+            Some(20),
         );
-        
+
         assert!(problem.is_ok());
         let problem = problem.unwrap();
         assert_eq!(problem.name, "Test Problem");
         assert_eq!(problem.creator_id, creator_id);
         assert_eq!(problem.difficulty, "V3");
         assert_eq!(problem.tags, vec!["overhang", "crimpy"]);
+        assert_eq!(problem.angle_degrees, Some(20));
         assert!(!problem.is_published);
         assert_eq!(problem.ascent_count, 0);
     }
 
+    #[test]
+    fn test_boulder_problem_creation_rejects_unrecognized_grade() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("start_1".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("hand_1".to_string(), HoldState::Used(HoldType::Hand));
+        config.add_hold("finish_1".to_string(), HoldState::Used(HoldType::Finish));
+
+        let problem = BoulderProblem::new(
+            "Test Problem".to_string(),
+            Uuid::new_v4(),
+            "not-a-grade".to_string(),
+            config,
+            vec![],
+            None,
+        );
+
+        assert!(matches!(problem, Err(BoulderProblemError::InvalidGrade(_))));
+    }
+
+    #[test]
+    fn test_boulder_problem_creation_accepts_font_scale_grade() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("start_1".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("hand_1".to_string(), HoldState::Used(HoldType::Hand));
+        config.add_hold("finish_1".to_string(), HoldState::Used(HoldType::Finish));
+
+        let problem = BoulderProblem::new(
+            "Test Problem".to_string(),
+            Uuid::new_v4(),
+            "7A".to_string(),
+            config,
+            vec![],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(problem.difficulty, "7A");
+    }
+
+    #[test]
+    fn test_graded_problem_parses_grade() {
+        let graded = GradedProblem::new("6B".to_string(), Some(40));
+        assert_eq!(graded.grade().unwrap().to_v_scale(), "V5");
+        assert_eq!(graded.angle_degrees, Some(40));
+    }
+
     #[test]
     fn test_valid_grades() {
         assert!(BoulderProblem::is_valid_grade("V0"));
@@ -381,6 +966,220 @@ mod tests {
         assert!(!BoulderProblem::is_valid_grade(""));
     }
 
+    #[test]
+    fn test_diagnostics_collects_multiple_issues_at_once() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("hand_1".to_string(), HoldState::Used(HoldType::Hand));
+
+        let diagnostics = config.diagnostics(None);
+        let codes: Vec<&str> = diagnostics.iter().map(|d| d.code).collect();
+
+        // No start, no finish, and too few holds overall: all three should be reported
+        // together, not just the first one found.
+        assert!(codes.contains(&"no-start-hold"));
+        assert!(codes.contains(&"no-finish-hold"));
+        assert!(codes.contains(&"too-few-holds"));
+        assert!(diagnostics.iter().any(|d| d.severity == Severity::Error));
+    }
+
+    #[test]
+    fn test_diagnostics_warns_on_no_foot_holds() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("start_1".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("finish_1".to_string(), HoldState::Used(HoldType::Finish));
+
+        let diagnostics = config.diagnostics(None);
+        let foot_warning = diagnostics
+            .iter()
+            .find(|d| d.code == "no-foot-holds")
+            .expect("expected a no-foot-holds warning");
+        assert_eq!(foot_warning.severity, Severity::Warning);
+
+        // Warnings alone shouldn't fail validation.
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_diagnostics_warns_on_sparse_holds_for_low_grade() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("start_1".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("foot_1".to_string(), HoldState::Used(HoldType::Foot));
+        config.add_hold("finish_1".to_string(), HoldState::Used(HoldType::Finish));
+
+        let diagnostics = config.diagnostics(Some("V0"));
+        assert!(diagnostics.iter().any(|d| d.code == "unreachable-for-grade"));
+
+        // A hard grade with the same sparse hold set shouldn't trigger the warning.
+        let diagnostics = config.diagnostics(Some("V10"));
+        assert!(!diagnostics.iter().any(|d| d.code == "unreachable-for-grade"));
+    }
+
+    #[test]
+    fn test_apply_fix_remove_hold() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("start_1".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("start_2".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("start_3".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("finish_1".to_string(), HoldState::Used(HoldType::Finish));
+
+        let diagnostics = config.diagnostics(None);
+        let fix = diagnostics
+            .iter()
+            .find_map(|d| d.fix.clone())
+            .expect("expected a fix for the too-many-start-holds diagnostic");
+
+        config.apply_fix(fix);
+        assert_eq!(config.get_holds_by_type(HoldType::Start).len(), 2);
+    }
+
+    #[test]
+    fn test_to_frames_sorted_by_placement_id() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("1185".to_string(), HoldState::Used(HoldType::Finish));
+        config.add_hold("1183".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("1184".to_string(), HoldState::Used(HoldType::Hand));
+
+        assert_eq!(config.to_frames(), "p1183r12p1184r13p1185r14");
+    }
+
+    #[test]
+    fn test_from_frames_parses_all_roles() {
+        let config = HoldConfiguration::from_frames("p1183r12p1184r13p1185r14p1186r15").unwrap();
+
+        assert_eq!(config.get_hold_state("1183"), HoldState::Used(HoldType::Start));
+        assert_eq!(config.get_hold_state("1184"), HoldState::Used(HoldType::Hand));
+        assert_eq!(config.get_hold_state("1185"), HoldState::Used(HoldType::Finish));
+        assert_eq!(config.get_hold_state("1186"), HoldState::Used(HoldType::Foot));
+    }
+
+    #[test]
+    fn test_frames_round_trip() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("1183".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("1184".to_string(), HoldState::Used(HoldType::Hand));
+        config.add_hold("1185".to_string(), HoldState::Used(HoldType::Finish));
+
+        let frames = config.to_frames();
+        let round_tripped = HoldConfiguration::from_frames(&frames).unwrap();
+
+        assert_eq!(round_tripped.holds.len(), config.holds.len());
+        assert_eq!(round_tripped.to_frames(), frames);
+    }
+
+    #[test]
+    fn test_from_frames_rejects_malformed_token() {
+        let err = HoldConfiguration::from_frames("p1183x12").unwrap_err();
+        assert!(matches!(err, FramesParseError::MalformedToken(_)));
+    }
+
+    #[test]
+    fn test_from_frames_rejects_unknown_role() {
+        let err = HoldConfiguration::from_frames("p1183r99").unwrap_err();
+        assert_eq!(err, FramesParseError::UnknownRole(99));
+    }
+
+    #[test]
+    fn test_create_request_resolves_frames() {
+        let request = CreateBoulderProblemRequest {
+            name: "Test".to_string(),
+            difficulty: "V3".to_string(),
+            hold_configuration: None,
+            frames: Some("p1183r12p1184r14".to_string()),
+            tags: None,
+            is_published: None,
+        };
+
+        let config = request.resolve_hold_configuration().unwrap();
+        assert_eq!(config.get_hold_state("1183"), HoldState::Used(HoldType::Start));
+    }
+
+    #[test]
+    fn test_create_request_rejects_when_neither_provided() {
+        let request = CreateBoulderProblemRequest {
+            name: "Test".to_string(),
+            difficulty: "V3".to_string(),
+            hold_configuration: None,
+            frames: None,
+            tags: None,
+            is_published: None,
+        };
+
+        assert!(matches!(
+            request.resolve_hold_configuration(),
+            Err(CreateProblemConfigError::Missing)
+        ));
+    }
+
+    fn test_layout() -> BoardLayout {
+        let mut layout = BoardLayout::new();
+        layout.positions.insert(
+            "1183".to_string(),
+            HoldPosition { x: 0.0, y: 0.0, mirror_of: Some("1184".to_string()) },
+        );
+        layout.positions.insert(
+            "1184".to_string(),
+            HoldPosition { x: 10.0, y: 0.0, mirror_of: Some("1183".to_string()) },
+        );
+        layout.positions.insert(
+            "1185".to_string(),
+            HoldPosition { x: 5.0, y: 1.0, mirror_of: None },
+        );
+        layout
+    }
+
+    #[test]
+    fn test_mirror_swaps_mirror_pairs_and_preserves_hold_type() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("1183".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("1185".to_string(), HoldState::Used(HoldType::Finish));
+
+        let mirrored = config.mirror(&test_layout());
+
+        assert_eq!(mirrored.get_hold_state("1184"), HoldState::Used(HoldType::Start));
+        // No mirror_of entry: stays on the same hold.
+        assert_eq!(mirrored.get_hold_state("1185"), HoldState::Used(HoldType::Finish));
+        assert_eq!(mirrored.get_hold_state("1183"), HoldState::NotUsed);
+    }
+
+    #[test]
+    fn test_bounding_box_and_span() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("1183".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("1184".to_string(), HoldState::Used(HoldType::Finish));
+
+        assert_eq!(config.bounding_box(&test_layout()), Some((0.0, 0.0, 10.0, 0.0)));
+        assert_eq!(config.span(&test_layout()), Some((10.0, 0.0)));
+    }
+
+    #[test]
+    fn test_bounding_box_none_without_known_positions() {
+        let mut config = HoldConfiguration::new();
+        config.add_hold("unknown_hold".to_string(), HoldState::Used(HoldType::Start));
+
+        assert_eq!(config.bounding_box(&BoardLayout::new()), None);
+    }
+
+    #[test]
+    fn test_diagnostics_with_layout_warns_on_unreachable_gap() {
+        let mut layout = test_layout();
+        layout.positions.insert(
+            "far_away".to_string(),
+            HoldPosition { x: 500.0, y: 500.0, mirror_of: None },
+        );
+
+        let mut config = HoldConfiguration::new();
+        config.add_hold("1183".to_string(), HoldState::Used(HoldType::Start));
+        config.add_hold("1184".to_string(), HoldState::Used(HoldType::Hand));
+        config.add_hold("far_away".to_string(), HoldState::Used(HoldType::Finish));
+
+        let diagnostics = config.diagnostics_with_layout(None, Some(&layout), DEFAULT_MAX_REACH);
+        let gap_warning = diagnostics
+            .iter()
+            .find(|d| d.code == "unreachable-gap")
+            .expect("expected an unreachable-gap warning for the isolated hold");
+        assert_eq!(gap_warning.severity, Severity::Warning);
+    }
+
     #[test]
     fn test_hold_configuration_serialization() {
         let mut config = HoldConfiguration::new();
@@ -0,0 +1,459 @@
+//! ActivityPub actor identity: keypair generation/sealing, actor document construction,
+//! WebFinger resolution, HTTP Signature signing/verification, and the Follow/Accept/Create
+//! activities that drive federation with other off-kilter (and compatible) instances.
+
+use rand::rngs::OsRng;
+use rsa::{
+    pkcs1v15::{SigningKey, VerifyingKey},
+    pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey, LineEnding},
+    signature::{RandomizedSigner, SignatureEncoding, Verifier},
+    RsaPrivateKey, RsaPublicKey,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::auth::totp::{open_secret, seal_secret};
+use crate::error::AppError;
+
+/// RSA modulus size for generated actor keys. 2048 bits is the size every major
+/// ActivityPub implementation (Mastodon, Plume, Pleroma) settled on for HTTP Signatures.
+const KEY_BITS: usize = 2048;
+
+/// A freshly generated actor keypair, ready to be sealed for storage.
+pub struct ActorKeypair {
+    pub public_key_pem: String,
+    private_key: RsaPrivateKey,
+}
+
+impl ActorKeypair {
+    /// Generate a new 2048-bit RSA keypair for a local actor.
+    pub fn generate() -> Self {
+        let private_key = RsaPrivateKey::new(&mut OsRng, KEY_BITS)
+            .expect("RSA key generation with a fixed, supported modulus cannot fail");
+        let public_key = RsaPublicKey::from(&private_key);
+        let public_key_pem = public_key
+            .to_public_key_pem(LineEnding::LF)
+            .expect("encoding a freshly generated public key cannot fail");
+
+        Self {
+            public_key_pem,
+            private_key,
+        }
+    }
+
+    /// Encrypt the private key for storage, the same AES-GCM construction
+    /// `auth::totp::seal_secret` uses for sealed TOTP secrets.
+    pub fn seal_private_key(&self, encryption_key: &[u8; 32]) -> String {
+        let der = self
+            .private_key
+            .to_pkcs8_der()
+            .expect("encoding a freshly generated private key cannot fail");
+        seal_secret(der.as_bytes(), encryption_key)
+    }
+}
+
+/// Load the symmetric key used to encrypt sealed actor private keys at rest, from
+/// `FEDERATION_KEY_ENCRYPTION_KEY` (32 raw bytes, base64-encoded). Falls back to a fixed dev
+/// key, same as `JwtConfig` and `auth::totp::encryption_key_from_env`.
+pub fn encryption_key_from_env() -> [u8; 32] {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    std::env::var("FEDERATION_KEY_ENCRYPTION_KEY")
+        .ok()
+        .and_then(|encoded| STANDARD.decode(encoded).ok())
+        .and_then(|bytes| bytes.try_into().ok())
+        .unwrap_or_else(|| {
+            tracing::warn!(
+                "FEDERATION_KEY_ENCRYPTION_KEY not set, using default (not secure for production)"
+            );
+            *b"federation-dev-key-change-prod!!"
+        })
+}
+
+/// Recover the private key sealed by [`ActorKeypair::seal_private_key`].
+pub fn open_private_key(sealed: &str, encryption_key: &[u8; 32]) -> Option<RsaPrivateKey> {
+    let der = open_secret(sealed, encryption_key)?;
+    RsaPrivateKey::from_pkcs8_der(&der).ok()
+}
+
+/// Sign `message` (conventionally the HTTP signature string built from the request's method,
+/// path, `host`, and `date` headers) with an actor's private key.
+pub fn sign(private_key: &RsaPrivateKey, message: &[u8]) -> Vec<u8> {
+    let signing_key = SigningKey::<Sha256>::new(private_key.clone());
+    signing_key.sign_with_rng(&mut OsRng, message).to_vec()
+}
+
+/// Verify a signature produced by [`sign`] against the claimed signer's public key PEM.
+pub fn verify(public_key_pem: &str, message: &[u8], signature: &[u8]) -> bool {
+    let Ok(public_key) = RsaPublicKey::from_public_key_pem(public_key_pem) else {
+        return false;
+    };
+    let verifying_key = VerifyingKey::<Sha256>::new(public_key);
+    let Ok(signature) = rsa::pkcs1v15::Signature::try_from(signature) else {
+        return false;
+    };
+    verifying_key.verify(message, &signature).is_ok()
+}
+
+/// A parsed `Signature` request header, per the HTTP Signatures draft ActivityPub builds on.
+/// `keyId` conventionally points at the signer's actor document, fragment-anchored to its
+/// `publicKey` block (e.g. `https://example.com/users/alex#main-key`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpSignature {
+    pub key_id: String,
+    /// The headers covered by `signature`, in the order they were signed in.
+    pub headers: Vec<String>,
+    pub signature: Vec<u8>,
+}
+
+/// Parse a `Signature: keyId="...",algorithm="...",headers="...",signature="..."` header
+/// value. `algorithm` is accepted but not inspected — this crate only ever verifies against
+/// [`verify`]'s RSA-SHA256, so a signer using a different one will just fail to verify rather
+/// than being rejected earlier with a clearer message.
+pub fn parse_signature_header(raw: &str) -> Option<HttpSignature> {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+    let mut key_id = None;
+    let mut headers = None;
+    let mut signature = None;
+
+    for field in raw.split(',') {
+        let (name, value) = field.split_once('=')?;
+        let value = value.trim().trim_matches('"');
+        match name.trim() {
+            "keyId" => key_id = Some(value.to_string()),
+            "headers" => headers = Some(value.split_whitespace().map(str::to_string).collect()),
+            "signature" => signature = STANDARD.decode(value).ok(),
+            _ => {}
+        }
+    }
+
+    Some(HttpSignature {
+        key_id: key_id?,
+        headers: headers.unwrap_or_else(|| vec!["(request-target)".to_string(), "date".to_string()]),
+        signature: signature?,
+    })
+}
+
+/// Strip `keyId`'s `#fragment`, yielding the actor document URL to fetch the signer's
+/// `publicKey` from.
+pub fn actor_url_from_key_id(key_id: &str) -> &str {
+    key_id.split('#').next().unwrap_or(key_id)
+}
+
+/// Reconstruct the signing string a [`parse_signature_header`]'s `signature` is supposed to
+/// cover: one line per entry in `signed_headers`, with the pseudo-header `(request-target)`
+/// rendered as `"<lowercased method> <path>"` and every other name looked up via
+/// `header_lookup` (expected to be case-insensitive, as `http::HeaderMap::get` is). Returns
+/// `None` if a named header the signer claims to have covered isn't present, since the
+/// signature can't have covered a value the verifier doesn't have.
+pub fn build_signing_string(
+    method: &str,
+    path: &str,
+    header_lookup: impl Fn(&str) -> Option<String>,
+    signed_headers: &[String],
+) -> Option<String> {
+    let mut lines = Vec::with_capacity(signed_headers.len());
+    for name in signed_headers {
+        if name == "(request-target)" {
+            lines.push(format!("(request-target): {} {path}", method.to_lowercase()));
+        } else {
+            lines.push(format!("{name}: {}", header_lookup(name)?));
+        }
+    }
+    Some(lines.join("\n"))
+}
+
+/// Fetch the `publicKeyPem` published in a remote actor's document, to verify the HTTP
+/// Signature on an activity it claims to have sent.
+pub async fn fetch_remote_public_key(actor_url: &str) -> Result<String, AppError> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(actor_url)
+        .header(reqwest::header::ACCEPT, "application/activity+json")
+        .send()
+        .await
+        .map_err(|e| AppError::Auth(format!("failed to fetch remote actor: {e}")))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::Auth(format!(
+            "remote actor fetch failed with status {}",
+            response.status()
+        )));
+    }
+
+    let actor: Actor = response
+        .json()
+        .await
+        .map_err(|e| AppError::Auth(format!("malformed remote actor document: {e}")))?;
+
+    Ok(actor.public_key.public_key_pem)
+}
+
+/// The `publicKey` block embedded in an actor document, per the `PublicKey` extension other
+/// implementations use to verify HTTP Signatures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActorPublicKey {
+    pub id: String,
+    pub owner: String,
+    #[serde(rename = "publicKeyPem")]
+    pub public_key_pem: String,
+}
+
+/// A minimal ActivityPub actor document (type `Person`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Actor {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub actor_type: String,
+    #[serde(rename = "preferredUsername")]
+    pub preferred_username: String,
+    pub inbox: String,
+    pub outbox: String,
+    pub followers: String,
+    pub following: String,
+    #[serde(rename = "publicKey")]
+    pub public_key: ActorPublicKey,
+}
+
+/// The ActivityStreams/ActivityPub JSON-LD context every document here is built against.
+fn activity_context() -> Vec<String> {
+    vec![
+        "https://www.w3.org/ns/activitystreams".to_string(),
+        "https://w3id.org/security/v1".to_string(),
+    ]
+}
+
+/// The actor URL for `username` on this instance, e.g. `https://example.com/ap/users/alex`.
+pub fn actor_url(base_url: &str, username: &str) -> String {
+    format!("{base_url}/ap/users/{username}")
+}
+
+/// Build the actor document served at [`actor_url`].
+pub fn build_actor(base_url: &str, username: &str, public_key_pem: &str) -> Actor {
+    let id = actor_url(base_url, username);
+    Actor {
+        context: activity_context(),
+        id: id.clone(),
+        actor_type: "Person".to_string(),
+        preferred_username: username.to_string(),
+        inbox: format!("{id}/inbox"),
+        outbox: format!("{id}/outbox"),
+        followers: format!("{id}/followers"),
+        following: format!("{id}/following"),
+        public_key: ActorPublicKey {
+            id: format!("{id}#main-key"),
+            owner: id,
+            public_key_pem: public_key_pem.to_string(),
+        },
+    }
+}
+
+/// A WebFinger `Link` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebFingerLink {
+    pub rel: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub link_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub href: Option<String>,
+}
+
+/// A `GET /.well-known/webfinger?resource=acct:user@host` response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebFingerResponse {
+    pub subject: String,
+    pub links: Vec<WebFingerLink>,
+}
+
+/// Parse a WebFinger `resource` query parameter of the form `acct:username@host` into its
+/// parts, rejecting anything else (e.g. bare URLs) this resolver doesn't support.
+pub fn parse_acct_resource(resource: &str) -> Option<(String, String)> {
+    let rest = resource.strip_prefix("acct:")?;
+    let (username, host) = rest.split_once('@')?;
+    if username.is_empty() || host.is_empty() {
+        return None;
+    }
+    Some((username.to_string(), host.to_string()))
+}
+
+/// Build the WebFinger response resolving `acct:username@host` to this instance's actor URL.
+pub fn build_webfinger_response(base_url: &str, host: &str, username: &str) -> WebFingerResponse {
+    WebFingerResponse {
+        subject: format!("acct:{username}@{host}"),
+        links: vec![WebFingerLink {
+            rel: "self".to_string(),
+            link_type: Some("application/activity+json".to_string()),
+            href: Some(actor_url(base_url, username)),
+        }],
+    }
+}
+
+/// An ActivityStreams activity, generic over its `object` payload. It carries no signature
+/// field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub actor: String,
+    pub object: serde_json::Value,
+}
+
+/// A `Follow` activity in which `actor_url` asks to follow `target_actor_url`.
+pub fn follow_activity(base_url: &str, actor_url: &str, target_actor_url: &str) -> Activity {
+    Activity {
+        context: activity_context(),
+        id: format!("{base_url}/activities/{}", uuid::Uuid::new_v4()),
+        activity_type: "Follow".to_string(),
+        actor: actor_url.to_string(),
+        object: serde_json::Value::String(target_actor_url.to_string()),
+    }
+}
+
+/// An `Accept` activity accepting a previously received `Follow`.
+pub fn accept_activity(base_url: &str, actor_url: &str, follow: &Activity) -> Activity {
+    Activity {
+        context: activity_context(),
+        id: format!("{base_url}/activities/{}", uuid::Uuid::new_v4()),
+        activity_type: "Accept".to_string(),
+        actor: actor_url.to_string(),
+        object: serde_json::to_value(follow).expect("Activity always serializes"),
+    }
+}
+
+/// A `Create` activity wrapping a custom `Ascent` object, published when a climber's send of
+/// `grade` (optionally on `problem_name`) is federated to their followers.
+pub fn ascent_activity(
+    base_url: &str,
+    actor_url: &str,
+    grade: &str,
+    problem_name: Option<&str>,
+) -> Activity {
+    let ascent = serde_json::json!({
+        "type": "Ascent",
+        "attributedTo": actor_url,
+        "grade": grade,
+        "problemName": problem_name,
+        "published": chrono::Utc::now().to_rfc3339(),
+    });
+
+    Activity {
+        context: activity_context(),
+        id: format!("{base_url}/activities/{}", uuid::Uuid::new_v4()),
+        activity_type: "Create".to_string(),
+        actor: actor_url.to_string(),
+        object: ascent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_acct_resource() {
+        assert_eq!(
+            parse_acct_resource("acct:alex@example.com"),
+            Some(("alex".to_string(), "example.com".to_string()))
+        );
+        assert_eq!(parse_acct_resource("https://example.com/users/alex"), None);
+        assert_eq!(parse_acct_resource("acct:@example.com"), None);
+    }
+
+    #[test]
+    fn test_sign_and_verify_round_trips() {
+        let keypair = ActorKeypair::generate();
+        let message = b"(request-target): post /users/alex/inbox";
+
+        let signature = sign(&keypair.private_key, message);
+        assert!(verify(&keypair.public_key_pem, message, &signature));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_message() {
+        let keypair = ActorKeypair::generate();
+        let signature = sign(&keypair.private_key, b"original message");
+
+        assert!(!verify(&keypair.public_key_pem, b"tampered message", &signature));
+    }
+
+    #[test]
+    fn test_seal_and_open_private_key_round_trips() {
+        let keypair = ActorKeypair::generate();
+        let key = encryption_key_from_env();
+
+        let sealed = keypair.seal_private_key(&key);
+        let opened = open_private_key(&sealed, &key).expect("should decrypt");
+
+        assert_eq!(
+            opened.to_pkcs8_der().unwrap().as_bytes(),
+            keypair.private_key.to_pkcs8_der().unwrap().as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_parse_signature_header_extracts_fields() {
+        let raw = r#"keyId="https://remote.example/users/alex#main-key",algorithm="rsa-sha256",headers="(request-target) host date",signature="aGVsbG8="#;
+        let parsed = parse_signature_header(raw).unwrap();
+
+        assert_eq!(parsed.key_id, "https://remote.example/users/alex#main-key");
+        assert_eq!(parsed.headers, vec!["(request-target)", "host", "date"]);
+        assert_eq!(parsed.signature, b"hello");
+    }
+
+    #[test]
+    fn test_parse_signature_header_rejects_missing_signature() {
+        assert!(parse_signature_header(r#"keyId="https://remote.example/users/alex#main-key""#).is_none());
+    }
+
+    #[test]
+    fn test_actor_url_from_key_id_strips_fragment() {
+        assert_eq!(
+            actor_url_from_key_id("https://remote.example/users/alex#main-key"),
+            "https://remote.example/users/alex"
+        );
+    }
+
+    #[test]
+    fn test_build_signing_string_renders_request_target_and_headers() {
+        let signed_headers = vec!["(request-target)".to_string(), "date".to_string()];
+        let signing_string = build_signing_string(
+            "POST",
+            "/ap/users/alex/inbox",
+            |name| (name == "date").then(|| "Tue, 01 Jul 2025 00:00:00 GMT".to_string()),
+            &signed_headers,
+        )
+        .unwrap();
+
+        assert_eq!(
+            signing_string,
+            "(request-target): post /ap/users/alex/inbox\ndate: Tue, 01 Jul 2025 00:00:00 GMT"
+        );
+    }
+
+    #[test]
+    fn test_build_signing_string_rejects_unavailable_header() {
+        let signed_headers = vec!["digest".to_string()];
+        assert!(build_signing_string("POST", "/inbox", |_| None, &signed_headers).is_none());
+    }
+
+    #[test]
+    fn test_sign_and_verify_round_trip_through_signing_string() {
+        let keypair = ActorKeypair::generate();
+        let signed_headers = vec!["(request-target)".to_string(), "date".to_string()];
+        let signing_string = build_signing_string(
+            "POST",
+            "/ap/users/alex/inbox",
+            |name| (name == "date").then(|| "Tue, 01 Jul 2025 00:00:00 GMT".to_string()),
+            &signed_headers,
+        )
+        .unwrap();
+
+        let signature = sign(&keypair.private_key, signing_string.as_bytes());
+        assert!(verify(&keypair.public_key_pem, signing_string.as_bytes(), &signature));
+    }
+}
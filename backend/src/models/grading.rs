@@ -0,0 +1,282 @@
+//! Pluggable grading-system support. Different gyms and guidebooks label boulder problems
+//! with different scales (V-scale, Font, YDS); this module maps any recognized grade onto a
+//! common integer "difficulty ordinal" so personal bests and grade distributions can be
+//! compared, stored, and converted without caring which scale a given grade was recorded in.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A supported bouldering grading system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GradingSystem {
+    VScale,
+    Font,
+    Yds,
+}
+
+impl GradingSystem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GradingSystem::VScale => "v_scale",
+            GradingSystem::Font => "font",
+            GradingSystem::Yds => "yds",
+        }
+    }
+}
+
+/// A grade parsed into its originating system and a difficulty ordinal comparable across
+/// systems.
+#[derive(Debug, Clone, Copy)]
+pub struct ParsedGrade {
+    pub system: GradingSystem,
+    pub ordinal: i32,
+}
+
+/// Error from [`Grade::parse`].
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("unrecognized grade: {0:?}")]
+pub struct GradeError(String);
+
+/// A grade that parsed successfully, carrying only the canonical ordinal so it can be
+/// re-rendered into any supported scale without re-parsing. Thin wrapper around
+/// [`parse_grade`]/[`format_ordinal`] — see those for the conversion tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grade {
+    ordinal: i32,
+}
+
+impl Grade {
+    /// Parse a grade string in any recognized system (V-scale, Font, or YDS).
+    pub fn parse(s: &str) -> Result<Self, GradeError> {
+        parse_grade(s)
+            .map(|parsed| Grade { ordinal: parsed.ordinal })
+            .ok_or_else(|| GradeError(s.to_string()))
+    }
+
+    /// The canonical difficulty ordinal, comparable across grading systems.
+    pub fn ordinal(&self) -> i32 {
+        self.ordinal
+    }
+
+    pub fn to_v_scale(&self) -> String {
+        format_ordinal(self.ordinal, GradingSystem::VScale)
+    }
+
+    pub fn to_font(&self) -> String {
+        format_ordinal(self.ordinal, GradingSystem::Font)
+    }
+
+    pub fn to_yds(&self) -> String {
+        format_ordinal(self.ordinal, GradingSystem::Yds)
+    }
+
+    /// Render into an arbitrary target system, for callers that only know the system as a
+    /// runtime value (e.g. a viewer's `preferred_grading_system`) rather than at the call site.
+    pub fn render(&self, system: GradingSystem) -> String {
+        format_ordinal(self.ordinal, system)
+    }
+}
+
+/// V-scale ordinals, indexed by V-grade number (V0 at index 0).
+const V_SCALE_ORDINALS: [i32; 18] = [
+    10, 13, 16, 19, 22, 25, 28, 31, 34, 37, 40, 43, 46, 49, 52, 55, 58, 61,
+];
+
+/// Font-scale grades aligned to [`V_SCALE_ORDINALS`], easiest to hardest, following the
+/// standard V/Font conversion chart.
+const FONT_SCALE: [(&str, i32); 18] = [
+    ("4", 10),
+    ("5", 13),
+    ("5+", 16),
+    ("6A", 19),
+    ("6A+", 22),
+    ("6B", 25),
+    ("6B+", 28),
+    ("6C", 31),
+    ("6C+", 34),
+    ("7A", 37),
+    ("7A+", 40),
+    ("7B", 43),
+    ("7B+", 46),
+    ("7C", 49),
+    ("7C+", 52),
+    ("8A", 55),
+    ("8A+", 58),
+    ("8B", 61),
+];
+
+/// YDS grades aligned to [`V_SCALE_ORDINALS`], following the standard conversion chart. YDS
+/// is normally a route (not boulder) scale, but some gyms echo it back for boulders anyway,
+/// so it's supported as a display option.
+const YDS_SCALE: [(&str, i32); 18] = [
+    ("5.8", 10),
+    ("5.9", 13),
+    ("5.10a", 16),
+    ("5.10b", 19),
+    ("5.10c", 22),
+    ("5.10d", 25),
+    ("5.11a", 28),
+    ("5.11b", 31),
+    ("5.11c", 34),
+    ("5.11d", 37),
+    ("5.12a", 40),
+    ("5.12b", 43),
+    ("5.12c", 46),
+    ("5.12d", 49),
+    ("5.13a", 52),
+    ("5.13b", 55),
+    ("5.13c", 58),
+    ("5.13d", 61),
+];
+
+/// Parse a grade string in any recognized system into a common difficulty ordinal.
+///
+/// V-scale half-grades like "V3/4" average the ordinals of their two endpoints. Unrecognized
+/// grades return `None` rather than silently defaulting to a bogus ordinal.
+pub fn parse_grade(grade: &str) -> Option<ParsedGrade> {
+    let trimmed = grade.trim();
+
+    if let Some(rest) = trimmed.strip_prefix('V').or_else(|| trimmed.strip_prefix('v')) {
+        return parse_v_scale(rest);
+    }
+
+    if let Some((_, ordinal)) = FONT_SCALE
+        .iter()
+        .find(|(label, _)| label.eq_ignore_ascii_case(trimmed))
+    {
+        return Some(ParsedGrade {
+            system: GradingSystem::Font,
+            ordinal: *ordinal,
+        });
+    }
+
+    if let Some((_, ordinal)) = YDS_SCALE
+        .iter()
+        .find(|(label, _)| label.eq_ignore_ascii_case(trimmed))
+    {
+        return Some(ParsedGrade {
+            system: GradingSystem::Yds,
+            ordinal: *ordinal,
+        });
+    }
+
+    None
+}
+
+fn parse_v_scale(rest: &str) -> Option<ParsedGrade> {
+    if let Some((low, high)) = rest.split_once('/') {
+        let low_ordinal = v_grade_ordinal(low)?;
+        let high_ordinal = v_grade_ordinal(high)?;
+        return Some(ParsedGrade {
+            system: GradingSystem::VScale,
+            ordinal: (low_ordinal + high_ordinal) / 2,
+        });
+    }
+
+    v_grade_ordinal(rest).map(|ordinal| ParsedGrade {
+        system: GradingSystem::VScale,
+        ordinal,
+    })
+}
+
+fn v_grade_ordinal(grade: &str) -> Option<i32> {
+    let grade_number: usize = grade.trim().parse().ok()?;
+    V_SCALE_ORDINALS.get(grade_number).copied()
+}
+
+/// Render a difficulty ordinal back into a grade label in the requested system, picking the
+/// closest defined grade.
+pub fn format_ordinal(ordinal: i32, system: GradingSystem) -> String {
+    match system {
+        GradingSystem::VScale => {
+            let index = V_SCALE_ORDINALS
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, value)| (*value - ordinal).abs())
+                .map(|(index, _)| index)
+                .unwrap_or(0);
+            format!("V{index}")
+        }
+        GradingSystem::Font => closest_label(&FONT_SCALE, ordinal),
+        GradingSystem::Yds => closest_label(&YDS_SCALE, ordinal),
+    }
+}
+
+fn closest_label(table: &[(&str, i32)], ordinal: i32) -> String {
+    table
+        .iter()
+        .min_by_key(|(_, value)| (*value - ordinal).abs())
+        .map(|(label, _)| label.to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_v_scale() {
+        let parsed = parse_grade("V5").unwrap();
+        assert_eq!(parsed.system, GradingSystem::VScale);
+        assert_eq!(parsed.ordinal, 25);
+    }
+
+    #[test]
+    fn test_parse_v_scale_half_grade_averages_endpoints() {
+        let parsed = parse_grade("V3/4").unwrap();
+        assert_eq!(parsed.ordinal, (19 + 22) / 2);
+    }
+
+    #[test]
+    fn test_parse_font_scale() {
+        let parsed = parse_grade("7A").unwrap();
+        assert_eq!(parsed.system, GradingSystem::Font);
+        assert_eq!(parsed.ordinal, 37);
+    }
+
+    #[test]
+    fn test_parse_yds() {
+        let parsed = parse_grade("5.12a").unwrap();
+        assert_eq!(parsed.system, GradingSystem::Yds);
+        assert_eq!(parsed.ordinal, 40);
+    }
+
+    #[test]
+    fn test_parse_unrecognized_grade_returns_none() {
+        assert!(parse_grade("not-a-grade").is_none());
+    }
+
+    #[test]
+    fn test_format_ordinal_round_trips_v_scale() {
+        assert_eq!(format_ordinal(25, GradingSystem::VScale), "V5");
+    }
+
+    #[test]
+    fn test_cross_scale_ordinals_align_on_the_conversion_chart() {
+        assert_eq!(
+            parse_grade("6B").unwrap().ordinal,
+            parse_grade("V5").unwrap().ordinal
+        );
+    }
+
+    #[test]
+    fn test_grade_parse_converts_between_scales() {
+        let grade = Grade::parse("V5").unwrap();
+        assert_eq!(grade.to_v_scale(), "V5");
+        assert_eq!(grade.to_font(), "6B");
+    }
+
+    #[test]
+    fn test_grade_parse_rejects_unrecognized_grade() {
+        assert!(Grade::parse("not-a-grade").is_err());
+    }
+
+    #[test]
+    fn test_grade_render_dispatches_to_requested_system() {
+        let grade = Grade::parse("V5").unwrap();
+        assert_eq!(grade.render(GradingSystem::VScale), grade.to_v_scale());
+        assert_eq!(grade.render(GradingSystem::Font), grade.to_font());
+        assert_eq!(grade.render(GradingSystem::Yds), grade.to_yds());
+    }
+}
@@ -0,0 +1,6 @@
+pub mod boulder_problem;
+pub mod federation;
+pub mod grading;
+pub mod relationship;
+pub mod user;
+pub mod verification;
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Status of a directed friend/follow request, stored per (requester, addressee) pair.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationshipStatus {
+    Pending,
+    Accepted,
+    Blocked,
+}
+
+impl RelationshipStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationshipStatus::Pending => "pending",
+            RelationshipStatus::Accepted => "accepted",
+            RelationshipStatus::Blocked => "blocked",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(RelationshipStatus::Pending),
+            "accepted" => Some(RelationshipStatus::Accepted),
+            "blocked" => Some(RelationshipStatus::Blocked),
+            _ => None,
+        }
+    }
+}
+
+/// A directed relationship row.
+#[derive(Debug, Clone)]
+pub struct Relationship {
+    pub requester_id: Uuid,
+    pub addressee_id: Uuid,
+    pub status: RelationshipStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The viewer-relative visibility tier used by the profile/statistics projection helpers.
+/// Collapses the directed `Relationship` (or its absence) into the three tiers that
+/// `PrivacySettings` already distinguishes between. This, together with the rest of this
+/// module and `repositories::relationship`, is the follower/friendship subsystem backing the
+/// `"friends"` visibility tier end to end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewerRelation {
+    /// The viewer is the profile owner; nothing is redacted.
+    Owner,
+    /// The viewer and owner have an accepted relationship in either direction.
+    Friend,
+    /// No relationship, or the viewer is anonymous.
+    Stranger,
+}
+
+impl ViewerRelation {
+    /// Does this viewer satisfy the given privacy tier?
+    pub fn satisfies(&self, visibility: &str) -> bool {
+        match visibility {
+            "public" => true,
+            "friends" => matches!(self, ViewerRelation::Owner | ViewerRelation::Friend),
+            _ => matches!(self, ViewerRelation::Owner),
+        }
+    }
+}
@@ -1,7 +1,90 @@
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use thiserror::Error;
 use uuid::Uuid;
-use chrono::{DateTime, Utc, Datelike};
+use chrono::{DateTime, Duration, Utc, Datelike};
+
+use crate::models::grading::{self, GradingSystem};
+use crate::models::relationship::ViewerRelation;
+
+/// Error from [`UserStatistics::record_attempt`].
+#[derive(Debug, Error)]
+pub enum RecordAttemptError {
+    /// The grade didn't parse in any supported system ([`GradingSystem`]), so there is no
+    /// difficulty ordinal to compare it against the personal best or the grade distribution.
+    #[error("unrecognized grade: {0:?}")]
+    UnrecognizedGrade(String),
+    #[error("failed to (de)serialize statistics data: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Where an account sits in its cradle-to-grave lifecycle, independent of the operator-set
+/// [`User::blocked`] flag. `Active` is the only status sensitive operations should run under;
+/// the rest exist to make the user-initiated and retention-driven paths to [`AccountStatus::SoftDeleted`]
+/// (and eventual hard purge) explicit instead of relying on row deletion alone.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountStatus {
+    Active,
+    Suspended,
+    Deactivated,
+    SoftDeleted,
+}
+
+impl AccountStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountStatus::Active => "active",
+            AccountStatus::Suspended => "suspended",
+            AccountStatus::Deactivated => "deactivated",
+            AccountStatus::SoftDeleted => "soft_deleted",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "active" => Some(AccountStatus::Active),
+            "suspended" => Some(AccountStatus::Suspended),
+            "deactivated" => Some(AccountStatus::Deactivated),
+            "soft_deleted" => Some(AccountStatus::SoftDeleted),
+            _ => None,
+        }
+    }
+}
+
+/// Error from an [`AccountStatus`] transition attempted from a status that doesn't allow it.
+#[derive(Debug, Error)]
+#[error("cannot {action} an account in status {status:?}")]
+pub struct AccountStatusError {
+    action: &'static str,
+    status: AccountStatus,
+}
+
+/// Error from [`User::new`]: `.0` didn't pass [`is_valid_email`].
+#[derive(Debug, Error)]
+#[error("invalid email address: {0:?}")]
+pub struct InvalidEmailError(String);
+
+/// A pragmatic `local@domain.tld` check (RFC 5322's full grammar is far more permissive than
+/// any real mail provider actually accepts), compiled once and reused for every signup.
+fn email_pattern() -> &'static Regex {
+    static PATTERN: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^[A-Za-z0-9.!#$%&'*+/=?^_`{|}~-]+@[A-Za-z0-9](?:[A-Za-z0-9-]*[A-Za-z0-9])?(?:\.[A-Za-z0-9](?:[A-Za-z0-9-]*[A-Za-z0-9])?)+$")
+            .expect("email_pattern is a valid regex")
+    })
+}
+
+/// Whether `email` looks like a deliverable address. Used by [`User::new`] and the
+/// registration handler so both paths reject the same malformed input.
+pub fn is_valid_email(email: &str) -> bool {
+    email_pattern().is_match(email)
+}
+
+/// How long a soft-deleted account is retained before [`User::can_purge`] allows a hard
+/// delete. A soft-deleted account cannot be restored through [`User::reactivate`].
+pub const SOFT_DELETE_RETENTION_DAYS: i64 = 30;
 
 /// Core user entity from the database
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -9,6 +92,28 @@ pub struct User {
     pub id: Uuid,
     pub email: String,
     pub username: String,
+    /// Set by an operator to immediately cut off the account. Checked on every authenticated
+    /// request (via a short-TTL cache, not a query per request) rather than only at login, so
+    /// existing sessions are revoked without waiting for their access token to expire.
+    pub blocked: bool,
+    /// Set once the owner has proven control of `email` by consuming an `EmailVerify`
+    /// `VerificationOtp`. `User::new` starts every account unverified; gate sensitive
+    /// operations on this via the `VerifiedUser` extractor rather than assuming registration
+    /// alone proves ownership.
+    pub verified: bool,
+    /// Where the account sits in its lifecycle; see [`AccountStatus`].
+    pub status: AccountStatus,
+    /// When `status` last became [`AccountStatus::SoftDeleted`], the clock [`User::can_purge`]
+    /// measures the retention window against. `None` unless the account has been soft-deleted.
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Display name, separate from `username` and the profile's `ProfileData::display_name`.
+    pub name: Option<String>,
+    pub avatar: Option<String>,
+    /// Grants access to staff-only operations (listing every account, blocking or unblocking
+    /// an account, purging expired soft-deleted accounts); no staff operation deletes another
+    /// user's account directly. See the `StaffUser` extractor, which is what every such
+    /// handler should gate on.
+    pub is_staff: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -42,6 +147,9 @@ pub struct ProfileData {
     pub avatar_url: Option<String>,
     pub location: Option<String>,
     pub preferred_units: Option<String>, // "metric" or "imperial"
+    /// Grading system grades are echoed back in (e.g. personal best). Defaults to whichever
+    /// system the underlying grade was originally recorded in when unset.
+    pub preferred_grading_system: Option<GradingSystem>,
     pub privacy_settings: PrivacySettings,
 }
 
@@ -68,9 +176,24 @@ impl Default for PrivacySettings {
 pub struct StatisticsData {
     pub grade_distribution: std::collections::HashMap<String, i32>,
     pub monthly_progress: Vec<MonthlyProgress>,
+    /// Streaks that have already closed (broken by a gap of more than a day). The streak
+    /// currently in progress lives in `current_streak` until it closes.
     pub streak_records: Vec<StreakRecord>,
     pub milestones: Vec<Milestone>,
+    /// The most-climbed problem types, most popular first. Derived from `problem_type_counts`
+    /// by [`record_problem_type`]; not edited directly.
     pub favorite_problem_types: Vec<String>,
+    /// The grading system the current `personal_best_grade` was originally recorded in,
+    /// stored alongside it so the correct conversion chart is used on display.
+    pub personal_best_system: Option<GradingSystem>,
+    /// Per-type successful-ascent tallies backing `favorite_problem_types`.
+    pub problem_type_counts: std::collections::HashMap<String, i32>,
+    /// The daily-climb streak currently in progress, if the most recent successful ascent
+    /// hasn't yet been followed by a gap of more than a day.
+    pub current_streak: Option<CurrentStreak>,
+    /// The longest daily-climb streak ever closed, so [`close_current_streak`] only fires a
+    /// `longest_streak` milestone on an actual new record.
+    pub longest_streak: i32,
 }
 
 /// Monthly climbing progress tracking
@@ -93,6 +216,15 @@ pub struct StreakRecord {
     pub last_activity: DateTime<Utc>,
 }
 
+/// The daily-climb streak currently in progress, tracked separately from [`StreakRecord`]
+/// (which only holds streaks that have already closed).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrentStreak {
+    pub start_date: DateTime<Utc>,
+    pub last_date: DateTime<Utc>,
+    pub length: i32,
+}
+
 /// Achievement milestones
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Milestone {
@@ -112,6 +244,8 @@ pub struct CreateUserRequest {
     pub email: String,
     pub username: String,
     pub password: String,
+    pub name: Option<String>,
+    pub avatar: Option<String>,
     pub profile: Option<ProfileData>,
 }
 
@@ -121,6 +255,42 @@ pub struct UpdateUserRequest {
     pub profile: Option<ProfileData>,
 }
 
+/// Login request against the password credential flow
+#[derive(Debug, Deserialize)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+    /// Required once the account has 2FA enabled; mutually exclusive with `recovery_code`.
+    pub totp_code: Option<String>,
+    /// A single-use recovery code, accepted in place of `totp_code`.
+    pub recovery_code: Option<String>,
+}
+
+/// Request to verify a TOTP code against a newly-enrolled (not yet enabled) secret
+#[derive(Debug, Deserialize)]
+pub struct VerifyTotpRequest {
+    pub code: String,
+}
+
+/// Request to disable 2FA, requiring a valid code as proof of possession
+#[derive(Debug, Deserialize)]
+pub struct DisableTotpRequest {
+    pub code: String,
+}
+
+/// Password change request for an already-authenticated user
+#[derive(Debug, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// Request to confirm an `EmailVerify` OTP sent to the caller's registered address
+#[derive(Debug, Deserialize)]
+pub struct ConfirmEmailVerificationRequest {
+    pub code: String,
+}
+
 /// Public user information (safe to expose)
 #[derive(Debug, Serialize)]
 pub struct PublicUser {
@@ -138,6 +308,11 @@ pub struct PublicStatistics {
     pub total_ascents: Option<i32>,
     pub personal_best_grade: Option<String>,
     pub grade_distribution: Option<std::collections::HashMap<String, i32>>,
+    /// Month-by-month attempt/ascent log, gated independently by
+    /// `privacy_settings.history_visibility` rather than `statistics_visibility` — a friend
+    /// can be let into the aggregate counts above without being shown the session-by-session
+    /// timeline, or vice versa.
+    pub attempt_history: Option<Vec<MonthlyProgress>>,
 }
 
 /// Complete user data with all related information
@@ -150,17 +325,115 @@ pub struct UserWithDetails {
 }
 
 impl User {
-    /// Create a new user (for registration)
-    pub fn new(email: String, username: String) -> Self {
+    /// Create a new user (for registration). Rejects a malformed `email` up front with a typed
+    /// error rather than persisting an address that can never receive the `EmailVerify` OTP.
+    pub fn new(email: String, username: String) -> Result<Self, InvalidEmailError> {
+        if !is_valid_email(&email) {
+            return Err(InvalidEmailError(email));
+        }
+
         let now = Utc::now();
-        Self {
+        Ok(Self {
             id: Uuid::new_v4(),
             email,
             username,
+            blocked: false,
+            verified: false,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            name: None,
+            avatar: None,
+            is_staff: false,
             created_at: now,
             updated_at: now,
+        })
+    }
+
+    /// Suspend the account (e.g. pending a moderation review). Only valid from `Active`.
+    pub fn suspend(&mut self) -> Result<(), AccountStatusError> {
+        self.transition_from_active(AccountStatus::Suspended, "suspend")
+    }
+
+    /// Deactivate the account at the owner's request. Only valid from `Active`.
+    pub fn deactivate(&mut self) -> Result<(), AccountStatusError> {
+        self.transition_from_active(AccountStatus::Deactivated, "deactivate")
+    }
+
+    /// Soft-delete the account, starting the [`SOFT_DELETE_RETENTION_DAYS`] retention window
+    /// after which it becomes eligible for a hard purge. Only valid from `Active`.
+    pub fn soft_delete(&mut self) -> Result<(), AccountStatusError> {
+        self.transition_from_active(AccountStatus::SoftDeleted, "soft_delete")?;
+        self.deleted_at = Some(self.updated_at);
+        Ok(())
+    }
+
+    /// Restore a `Suspended` or `Deactivated` account to `Active`. A `SoftDeleted` account
+    /// cannot be reactivated this way — the Uuid and timestamps are preserved, but the
+    /// retention-then-purge path is one-directional by design, so a purged account can never
+    /// come back.
+    pub fn reactivate(&mut self) -> Result<(), AccountStatusError> {
+        match self.status {
+            AccountStatus::Suspended | AccountStatus::Deactivated => {
+                self.status = AccountStatus::Active;
+                self.updated_at = Utc::now();
+                Ok(())
+            }
+            status => Err(AccountStatusError {
+                action: "reactivate",
+                status,
+            }),
         }
     }
+
+    /// Whether a soft-deleted account has sat past its retention window and may be hard
+    /// purged. Always `false` for an account that was never soft-deleted.
+    pub fn can_purge(&self, now: DateTime<Utc>) -> bool {
+        self.status == AccountStatus::SoftDeleted
+            && self
+                .deleted_at
+                .is_some_and(|deleted_at| now - deleted_at >= chrono::Duration::days(SOFT_DELETE_RETENTION_DAYS))
+    }
+
+    fn transition_from_active(
+        &mut self,
+        to: AccountStatus,
+        action: &'static str,
+    ) -> Result<(), AccountStatusError> {
+        if self.status != AccountStatus::Active {
+            return Err(AccountStatusError {
+                action,
+                status: self.status,
+            });
+        }
+        self.status = to;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Serialize this user plus their profile and statistics into a single portable JSON
+    /// bundle, for a GDPR Article 20 data-portability request.
+    pub fn export_data(
+        &self,
+        profile: &UserProfile,
+        statistics: &UserStatistics,
+    ) -> Result<String, serde_json::Error> {
+        let bundle = AccountDataExport {
+            user: self.clone(),
+            profile: profile.get_profile_data()?,
+            statistics: statistics.get_statistics_data()?,
+            exported_at: Utc::now(),
+        };
+        serde_json::to_string(&bundle)
+    }
+}
+
+/// Portable data bundle produced by [`User::export_data`].
+#[derive(Debug, Serialize)]
+pub struct AccountDataExport {
+    pub user: User,
+    pub profile: ProfileData,
+    pub statistics: StatisticsData,
+    pub exported_at: DateTime<Utc>,
 }
 
 impl UserProfile {
@@ -184,6 +457,41 @@ impl UserProfile {
         self.updated_at = Utc::now();
         Ok(())
     }
+
+    /// The profile as `relation` is entitled to see it, per `privacy_settings.profile_visibility`.
+    pub fn view_as(&self, relation: ViewerRelation) -> Result<ProfileData, serde_json::Error> {
+        Ok(self.get_profile_data()?.view_as(relation))
+    }
+}
+
+impl ProfileData {
+    /// Redact fields `relation` isn't entitled to see, per `privacy_settings.profile_visibility`.
+    /// The owner's own view is never redacted; `friends` exposes a reduced field set to
+    /// confirmed friends; anyone below the required tier gets only a placeholder display name.
+    pub fn view_as(&self, relation: ViewerRelation) -> ProfileData {
+        if relation == ViewerRelation::Owner {
+            return self.clone();
+        }
+
+        match self.privacy_settings.profile_visibility.as_str() {
+            "public" => self.clone(),
+            "friends" if relation == ViewerRelation::Friend => ProfileData {
+                first_name: None,
+                last_name: None,
+                display_name: self.display_name.clone(),
+                bio: None,
+                avatar_url: self.avatar_url.clone(),
+                location: None,
+                preferred_units: None,
+                preferred_grading_system: self.preferred_grading_system,
+                privacy_settings: self.privacy_settings.clone(),
+            },
+            _ => ProfileData {
+                display_name: Some("Private User".to_string()),
+                ..Default::default()
+            },
+        }
+    }
 }
 
 impl UserStatistics {
@@ -204,29 +512,61 @@ impl UserStatistics {
         serde_json::from_value(self.statistics_data.clone())
     }
 
-    /// Update statistics after an attempt
-    pub fn record_attempt(&mut self, grade: &str, success: bool) -> Result<(), serde_json::Error> {
+    /// Update statistics after an attempt made at `attempted_at`, optionally tagged with the
+    /// problem's `problem_type` (e.g. "slab", "overhang") for the favorites tally. Rejects
+    /// grades that don't parse in any supported system ([`grading::parse_grade`]) instead of
+    /// silently recording them at a bogus difficulty, which would corrupt `personal_best_grade`
+    /// comparisons across scales.
+    pub fn record_attempt(
+        &mut self,
+        grade: &str,
+        success: bool,
+        attempted_at: DateTime<Utc>,
+        problem_type: Option<&str>,
+    ) -> Result<(), RecordAttemptError> {
+        let parsed_grade = grading::parse_grade(grade)
+            .ok_or_else(|| RecordAttemptError::UnrecognizedGrade(grade.to_string()))?;
+
         self.total_attempts += 1;
-        
+
+        // Update extended statistics
+        let mut stats_data = self.get_statistics_data()?;
+
         if success {
             self.total_ascents += 1;
-            
-            // Update personal best if this is a harder grade
+
+            // Update personal best if this is a harder grade, comparing by difficulty
+            // ordinal so grades from different scales (V-scale, Font, YDS) compare correctly.
             if self.personal_best_grade.is_none() || self.is_harder_grade(grade) {
                 self.personal_best_grade = Some(grade.to_string());
+                stats_data.personal_best_system = Some(parsed_grade.system);
+                stats_data.milestones.push(Milestone {
+                    id: format!("first_ascent_at_grade_{grade}"),
+                    name: format!("First ascent at {grade}"),
+                    description: format!("Reached a new personal best grade: {grade}"),
+                    category: "grades".to_string(),
+                    achieved_at: attempted_at,
+                    value: parsed_grade.ordinal,
+                });
+            }
+
+            record_ascent_milestones(&mut stats_data, self.total_ascents, attempted_at);
+            record_streak(&mut stats_data, attempted_at);
+
+            if let Some(problem_type) = problem_type {
+                record_problem_type(&mut stats_data, problem_type);
             }
         }
 
-        // Update extended statistics
-        let mut stats_data = self.get_statistics_data()?;
-        
-        // Update grade distribution
-        *stats_data.grade_distribution.entry(grade.to_string()).or_insert(0) += 1;
-        
+        // Update grade distribution, keyed by the canonical V-scale label rather than the
+        // as-recorded string, so e.g. "V4" and "6B+" (the same difficulty on the conversion
+        // chart) land in the same bucket instead of being double-counted.
+        let canonical_grade = grading::format_ordinal(parsed_grade.ordinal, GradingSystem::VScale);
+        *stats_data.grade_distribution.entry(canonical_grade).or_insert(0) += 1;
+
         // Update monthly progress
-        let now = Utc::now();
         if let Some(current_month) = stats_data.monthly_progress.last_mut() {
-            if current_month.year == now.year() && current_month.month == now.month() as i32 {
+            if current_month.year == attempted_at.year() && current_month.month == attempted_at.month() as i32 {
                 current_month.attempts += 1;
                 if success {
                     current_month.ascents += 1;
@@ -234,8 +574,8 @@ impl UserStatistics {
             } else {
                 // New month
                 stats_data.monthly_progress.push(MonthlyProgress {
-                    year: now.year(),
-                    month: now.month() as i32,
+                    year: attempted_at.year(),
+                    month: attempted_at.month() as i32,
                     attempts: 1,
                     ascents: if success { 1 } else { 0 },
                     unique_problems: 1,
@@ -244,8 +584,8 @@ impl UserStatistics {
         } else {
             // First entry
             stats_data.monthly_progress.push(MonthlyProgress {
-                year: now.year(),
-                month: now.month() as i32,
+                year: attempted_at.year(),
+                month: attempted_at.month() as i32,
                 attempts: 1,
                 ascents: if success { 1 } else { 0 },
                 unique_problems: 1,
@@ -257,31 +597,212 @@ impl UserStatistics {
         Ok(())
     }
 
-    /// Check if a grade is harder than the current personal best
+    /// The statistics as `relation` is entitled to see them, per `visibility` (the owning
+    /// profile's `privacy_settings.statistics_visibility`) and `history_visibility` (its
+    /// `privacy_settings.history_visibility`, gating `attempt_history` independently of the
+    /// aggregate fields). Below a field's required tier it comes back `None` rather than
+    /// omitted, so callers always get a complete `PublicStatistics` shape to serialize.
+    /// `preferred_system` re-renders the personal best, and every label in
+    /// `grade_distribution`, in the viewer's preferred grading system; the personal best falls
+    /// back to whichever system it was originally recorded in, and the distribution falls back
+    /// to its canonical V-scale storage form.
+    pub fn view_as(
+        &self,
+        relation: ViewerRelation,
+        visibility: &str,
+        history_visibility: &str,
+        preferred_system: Option<GradingSystem>,
+    ) -> Result<PublicStatistics, serde_json::Error> {
+        if !relation.satisfies(visibility) {
+            return Ok(PublicStatistics {
+                total_attempts: None,
+                total_ascents: None,
+                personal_best_grade: None,
+                grade_distribution: None,
+                attempt_history: None,
+            });
+        }
+
+        let stats_data = self.get_statistics_data()?;
+        let personal_best_grade = self.personal_best_grade.as_deref().and_then(|grade| {
+            let parsed = grading::parse_grade(grade)?;
+            let system = preferred_system.unwrap_or(parsed.system);
+            Some(grading::format_ordinal(parsed.ordinal, system))
+        });
+
+        let attempt_history = relation
+            .satisfies(history_visibility)
+            .then(|| stats_data.monthly_progress.clone());
+
+        let grade_distribution = match preferred_system {
+            Some(system) if system != GradingSystem::VScale => {
+                render_grade_distribution(&stats_data.grade_distribution, system)
+            }
+            _ => stats_data.grade_distribution,
+        };
+
+        Ok(PublicStatistics {
+            total_attempts: Some(self.total_attempts),
+            total_ascents: Some(self.total_ascents),
+            personal_best_grade,
+            grade_distribution: Some(grade_distribution),
+            attempt_history,
+        })
+    }
+
+    /// Check if a grade is harder than the current personal best, comparing difficulty
+    /// ordinals so grades recorded in different scales (V-scale, Font, YDS) compare
+    /// correctly. An unrecognized grade never displaces a recognized personal best.
     fn is_harder_grade(&self, grade: &str) -> bool {
         if let Some(current_best) = &self.personal_best_grade {
-            // Simple V-scale comparison (V0 < V1 < V2 ... < V17)
-            let current_num = current_best.trim_start_matches('V').parse::<i32>().unwrap_or(0);
-            let new_num = grade.trim_start_matches('V').parse::<i32>().unwrap_or(0);
-            new_num > current_num
+            let Some(new_parsed) = grading::parse_grade(grade) else {
+                return false;
+            };
+            let current_ordinal = grading::parse_grade(current_best).map(|parsed| parsed.ordinal).unwrap_or(0);
+            new_parsed.ordinal > current_ordinal
         } else {
             true
         }
     }
 }
 
+/// Re-key a canonical (V-scale) grade distribution into `system`, summing counts that land on
+/// the same rendered label (e.g. two adjacent V-scale grades can round to the same Font grade).
+fn render_grade_distribution(
+    canonical: &std::collections::HashMap<String, i32>,
+    system: GradingSystem,
+) -> std::collections::HashMap<String, i32> {
+    let mut rendered = std::collections::HashMap::new();
+    for (grade, count) in canonical {
+        let Some(parsed) = grading::parse_grade(grade) else {
+            continue;
+        };
+        let label = grading::format_ordinal(parsed.ordinal, system);
+        *rendered.entry(label).or_insert(0) += count;
+    }
+    rendered
+}
+
+/// Ascent-count milestone thresholds, beyond the always-fired first ascent.
+const ASCENT_MILESTONE_THRESHOLDS: [i32; 4] = [10, 25, 50, 100];
+
+/// Fire the "first ascent" milestone, and the Nth-ascent milestone if `total_ascents` lands on
+/// one of [`ASCENT_MILESTONE_THRESHOLDS`].
+fn record_ascent_milestones(stats_data: &mut StatisticsData, total_ascents: i32, at: DateTime<Utc>) {
+    if total_ascents == 1 {
+        stats_data.milestones.push(Milestone {
+            id: "first_ascent".to_string(),
+            name: "First ascent".to_string(),
+            description: "Logged your first successful ascent".to_string(),
+            category: "ascents".to_string(),
+            achieved_at: at,
+            value: 1,
+        });
+    }
+
+    if ASCENT_MILESTONE_THRESHOLDS.contains(&total_ascents) {
+        stats_data.milestones.push(Milestone {
+            id: format!("ascent_count_{total_ascents}"),
+            name: format!("{total_ascents} ascents"),
+            description: format!("Logged your {total_ascents}th successful ascent"),
+            category: "ascents".to_string(),
+            achieved_at: at,
+            value: total_ascents,
+        });
+    }
+}
+
+/// Extend, leave unchanged, or close the daily-climb streak in progress, given a successful
+/// ascent at `at`. Same calendar day as the last ascent: unchanged. The very next day: extends
+/// by one. Any larger gap: closes the old streak into `streak_records` (see
+/// [`close_current_streak`]) and starts a new one at length 1. An ascent backdated before the
+/// last one is left alone rather than un-extending the streak.
+fn record_streak(stats_data: &mut StatisticsData, at: DateTime<Utc>) {
+    let today = at.date_naive();
+
+    let Some(streak) = stats_data.current_streak.as_mut() else {
+        stats_data.current_streak = Some(CurrentStreak {
+            start_date: at,
+            last_date: at,
+            length: 1,
+        });
+        return;
+    };
+
+    let last_day = streak.last_date.date_naive();
+    if today == last_day + Duration::days(1) {
+        streak.length += 1;
+        streak.last_date = at;
+    } else if today > last_day + Duration::days(1) {
+        close_current_streak(stats_data);
+        stats_data.current_streak = Some(CurrentStreak {
+            start_date: at,
+            last_date: at,
+            length: 1,
+        });
+    }
+    // today == last_day: same-day ascent, streak unchanged.
+    // today < last_day: backdated ascent, left alone.
+}
+
+/// Close the in-progress streak (if any) into `streak_records`, firing a `longest_streak`
+/// milestone when it's a new record.
+fn close_current_streak(stats_data: &mut StatisticsData) {
+    let Some(streak) = stats_data.current_streak.take() else {
+        return;
+    };
+
+    stats_data.streak_records.push(StreakRecord {
+        streak_type: "daily_climb".to_string(),
+        current_count: streak.length,
+        best_count: streak.length,
+        start_date: streak.start_date,
+        last_activity: streak.last_date,
+    });
+
+    if streak.length > stats_data.longest_streak {
+        stats_data.longest_streak = streak.length;
+        stats_data.milestones.push(Milestone {
+            id: format!("longest_streak_{}", streak.length),
+            name: format!("{}-day streak", streak.length),
+            description: format!("Climbed on {} consecutive days", streak.length),
+            category: "streaks".to_string(),
+            achieved_at: streak.last_date,
+            value: streak.length,
+        });
+    }
+}
+
+/// Bump `problem_type`'s ascent tally and recompute `favorite_problem_types` as the top 5
+/// types by count, ties broken alphabetically for a deterministic ordering.
+fn record_problem_type(stats_data: &mut StatisticsData, problem_type: &str) {
+    *stats_data
+        .problem_type_counts
+        .entry(problem_type.to_string())
+        .or_insert(0) += 1;
+
+    let mut counts: Vec<(&String, &i32)> = stats_data.problem_type_counts.iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    stats_data.favorite_problem_types = counts.into_iter().take(5).map(|(name, _)| name.clone()).collect();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_user_creation() {
-        let user = User::new("test@example.com".to_string(), "testuser".to_string());
+        let user = User::new("test@example.com".to_string(), "testuser".to_string()).unwrap();
         assert_eq!(user.email, "test@example.com");
         assert_eq!(user.username, "testuser");
         assert!(!user.id.is_nil());
     }
 
+    #[test]
+    fn test_user_new_rejects_malformed_email() {
+        assert!(User::new("not-an-email".to_string(), "testuser".to_string()).is_err());
+    }
+
     #[test]
     fn test_user_profile_creation() {
         let user_id = Uuid::new_v4();
@@ -303,21 +824,22 @@ mod tests {
     fn test_statistics_attempt_recording() {
         let user_id = Uuid::new_v4();
         let mut stats = UserStatistics::new(user_id);
-        
+        let now = Utc::now();
+
         // Record a successful attempt
-        stats.record_attempt("V3", true).unwrap();
+        stats.record_attempt("V3", true, now, None).unwrap();
         assert_eq!(stats.total_attempts, 1);
         assert_eq!(stats.total_ascents, 1);
         assert_eq!(stats.personal_best_grade, Some("V3".to_string()));
-        
+
         // Record a failed attempt
-        stats.record_attempt("V5", false).unwrap();
+        stats.record_attempt("V5", false, now, None).unwrap();
         assert_eq!(stats.total_attempts, 2);
         assert_eq!(stats.total_ascents, 1);
         assert_eq!(stats.personal_best_grade, Some("V3".to_string())); // Unchanged
-        
+
         // Record a harder successful attempt
-        stats.record_attempt("V4", true).unwrap();
+        stats.record_attempt("V4", true, now, None).unwrap();
         assert_eq!(stats.total_attempts, 3);
         assert_eq!(stats.total_ascents, 2);
         assert_eq!(stats.personal_best_grade, Some("V4".to_string())); // Updated
@@ -327,10 +849,186 @@ mod tests {
     fn test_grade_comparison() {
         let user_id = Uuid::new_v4();
         let mut stats = UserStatistics::new(user_id);
-        
-        stats.record_attempt("V2", true).unwrap();
+
+        stats.record_attempt("V2", true, Utc::now(), None).unwrap();
         assert!(stats.is_harder_grade("V3"));
         assert!(!stats.is_harder_grade("V1"));
         assert!(!stats.is_harder_grade("V2"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_grade_comparison_across_scales() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+
+        // V5 and 6B sit at the same point on the conversion chart, so neither displaces
+        // the other, but 6B+ is one step harder than V5.
+        stats.record_attempt("V5", true, Utc::now(), None).unwrap();
+        assert!(!stats.is_harder_grade("6B"));
+        assert!(stats.is_harder_grade("6B+"));
+    }
+
+    #[test]
+    fn test_personal_best_system_recorded_alongside_grade() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+
+        stats.record_attempt("7A", true, Utc::now(), None).unwrap();
+        let stats_data = stats.get_statistics_data().unwrap();
+        assert_eq!(stats_data.personal_best_system, Some(GradingSystem::Font));
+    }
+
+    #[test]
+    fn test_record_attempt_rejects_unrecognized_grade() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+
+        let result = stats.record_attempt("not-a-grade", true, Utc::now(), None);
+        assert!(matches!(result, Err(RecordAttemptError::UnrecognizedGrade(_))));
+        // A rejected attempt shouldn't be counted.
+        assert_eq!(stats.total_attempts, 0);
+    }
+
+    #[test]
+    fn test_record_attempt_accepts_grades_across_scales() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+
+        stats.record_attempt("7A", true, Utc::now(), None).unwrap();
+        stats.record_attempt("5.12a", true, Utc::now(), None).unwrap();
+        assert_eq!(stats.total_attempts, 2);
+        assert_eq!(stats.total_ascents, 2);
+    }
+
+    #[test]
+    fn test_first_ascent_and_nth_ascent_milestones_fire() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+        let start = Utc::now();
+
+        for day in 0..10 {
+            stats
+                .record_attempt("V3", true, start + Duration::days(day), None)
+                .unwrap();
+        }
+
+        let stats_data = stats.get_statistics_data().unwrap();
+        assert!(stats_data.milestones.iter().any(|m| m.id == "first_ascent"));
+        assert!(stats_data.milestones.iter().any(|m| m.id == "ascent_count_10"));
+    }
+
+    #[test]
+    fn test_new_personal_best_fires_grade_milestone() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+
+        stats.record_attempt("V3", true, Utc::now(), None).unwrap();
+        stats.record_attempt("V4", true, Utc::now(), None).unwrap();
+
+        let stats_data = stats.get_statistics_data().unwrap();
+        assert!(stats_data
+            .milestones
+            .iter()
+            .any(|m| m.id == "first_ascent_at_grade_V3"));
+        assert!(stats_data
+            .milestones
+            .iter()
+            .any(|m| m.id == "first_ascent_at_grade_V4"));
+    }
+
+    #[test]
+    fn test_streak_extends_on_consecutive_days_and_is_unchanged_same_day() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+        let day_one = Utc::now();
+
+        stats.record_attempt("V3", true, day_one, None).unwrap();
+        stats.record_attempt("V4", true, day_one, None).unwrap(); // same day, no change
+        stats
+            .record_attempt("V3", true, day_one + Duration::days(1), None)
+            .unwrap();
+
+        let stats_data = stats.get_statistics_data().unwrap();
+        let streak = stats_data.current_streak.expect("streak in progress");
+        assert_eq!(streak.length, 2);
+        assert!(stats_data.streak_records.is_empty());
+    }
+
+    #[test]
+    fn test_streak_closes_and_fires_longest_streak_milestone_on_a_gap() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+        let day_one = Utc::now();
+
+        stats.record_attempt("V3", true, day_one, None).unwrap();
+        stats
+            .record_attempt("V3", true, day_one + Duration::days(1), None)
+            .unwrap();
+        // Gap of 3 days closes the 2-day streak and starts a new one.
+        stats
+            .record_attempt("V3", true, day_one + Duration::days(4), None)
+            .unwrap();
+
+        let stats_data = stats.get_statistics_data().unwrap();
+        assert_eq!(stats_data.streak_records.len(), 1);
+        assert_eq!(stats_data.streak_records[0].current_count, 2);
+        assert_eq!(stats_data.longest_streak, 2);
+        assert!(stats_data
+            .milestones
+            .iter()
+            .any(|m| m.id == "longest_streak_2"));
+        assert_eq!(stats_data.current_streak.unwrap().length, 1);
+    }
+
+    #[test]
+    fn test_favorite_problem_types_ranks_by_ascent_count() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+
+        stats.record_attempt("V3", true, Utc::now(), Some("slab")).unwrap();
+        stats.record_attempt("V4", true, Utc::now(), Some("slab")).unwrap();
+        stats
+            .record_attempt("V5", true, Utc::now(), Some("overhang"))
+            .unwrap();
+
+        let stats_data = stats.get_statistics_data().unwrap();
+        assert_eq!(stats_data.favorite_problem_types, vec!["slab", "overhang"]);
+    }
+
+    #[test]
+    fn test_grade_distribution_is_keyed_by_canonical_grade_across_scales() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+
+        // "V4" and "6B+" sit at the same point on the conversion chart, so they should land
+        // in the same grade_distribution bucket instead of being counted separately.
+        stats.record_attempt("V4", true, Utc::now(), None).unwrap();
+        stats.record_attempt("6B+", true, Utc::now(), None).unwrap();
+
+        let stats_data = stats.get_statistics_data().unwrap();
+        assert_eq!(stats_data.grade_distribution.len(), 1);
+        assert_eq!(stats_data.grade_distribution.get("V4"), Some(&2));
+    }
+
+    #[test]
+    fn test_view_as_renders_grade_distribution_in_preferred_system() {
+        let user_id = Uuid::new_v4();
+        let mut stats = UserStatistics::new(user_id);
+
+        stats.record_attempt("V4", true, Utc::now(), None).unwrap();
+
+        let public_stats = stats
+            .view_as(ViewerRelation::Owner, "public", "public", Some(GradingSystem::Font))
+            .unwrap();
+
+        let grade_distribution = public_stats.grade_distribution.unwrap();
+        assert_eq!(grade_distribution.len(), 1);
+        assert_eq!(
+            grade_distribution.get(&grading::format_ordinal(
+                grading::parse_grade("V4").unwrap().ordinal,
+                GradingSystem::Font
+            )),
+            Some(&1)
+        );
+    }
+}
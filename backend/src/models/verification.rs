@@ -0,0 +1,140 @@
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// What a `VerificationOtp` proves, scoped into the row alongside the user id so a code
+/// minted for one purpose can never be replayed to complete another.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationPurpose {
+    EmailVerify,
+    PasswordReset,
+    EmailChange,
+}
+
+impl VerificationPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationPurpose::EmailVerify => "email_verify",
+            VerificationPurpose::PasswordReset => "password_reset",
+            VerificationPurpose::EmailChange => "email_change",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "email_verify" => Some(VerificationPurpose::EmailVerify),
+            "password_reset" => Some(VerificationPurpose::PasswordReset),
+            "email_change" => Some(VerificationPurpose::EmailChange),
+            _ => None,
+        }
+    }
+}
+
+/// How long a freshly generated OTP stays valid before `consume` rejects it as stale.
+pub const OTP_VALIDITY_MINUTES: i64 = 15;
+
+/// A single-use one-time code proving a user controls the channel (their registered email)
+/// that `purpose` operates on. Modeled on the TOTP recovery-code rows: only `code_hash` is
+/// ever stored, and `used_at` makes a second `consume` of the same row fail instead of being
+/// silently accepted twice.
+#[derive(Debug, Clone)]
+pub struct VerificationOtp {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub purpose: VerificationPurpose,
+    pub code_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl VerificationOtp {
+    /// Whether this OTP can still be consumed: unused and not past its validity window.
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        self.used_at.is_none() && self.expires_at > now
+    }
+}
+
+/// Generate a new 6-digit numeric code, plaintext (to be emailed once) alongside the hash
+/// that gets stored.
+pub fn generate_code() -> String {
+    let mut bytes = [0u8; 4];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let value = u32::from_be_bytes(bytes) % 1_000_000;
+    format!("{value:06}")
+}
+
+/// Hash a presented code for storage/lookup, the same construction as
+/// `auth::jwt::hash_refresh_token`.
+pub fn hash_code(code: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(code.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// The expiry timestamp for a code minted at `now`.
+pub fn expiry_from(now: DateTime<Utc>) -> DateTime<Utc> {
+    now + Duration::minutes(OTP_VALIDITY_MINUTES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_purpose_round_trips_through_str() {
+        for purpose in [
+            VerificationPurpose::EmailVerify,
+            VerificationPurpose::PasswordReset,
+            VerificationPurpose::EmailChange,
+        ] {
+            assert_eq!(VerificationPurpose::from_str(purpose.as_str()), Some(purpose));
+        }
+    }
+
+    #[test]
+    fn test_generated_code_is_six_digits() {
+        let code = generate_code();
+        assert_eq!(code.len(), 6);
+        assert!(code.chars().all(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_hash_code_is_deterministic() {
+        let code = generate_code();
+        assert_eq!(hash_code(&code), hash_code(&code));
+    }
+
+    #[test]
+    fn test_is_valid_at_rejects_expired() {
+        let now = Utc::now();
+        let otp = VerificationOtp {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            purpose: VerificationPurpose::EmailVerify,
+            code_hash: hash_code("123456"),
+            expires_at: now - Duration::minutes(1),
+            used_at: None,
+            created_at: now - Duration::minutes(OTP_VALIDITY_MINUTES + 1),
+        };
+        assert!(!otp.is_valid_at(now));
+    }
+
+    #[test]
+    fn test_is_valid_at_rejects_used() {
+        let now = Utc::now();
+        let otp = VerificationOtp {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            purpose: VerificationPurpose::EmailVerify,
+            code_hash: hash_code("123456"),
+            expires_at: expiry_from(now),
+            used_at: Some(now),
+            created_at: now,
+        };
+        assert!(!otp.is_valid_at(now));
+    }
+}
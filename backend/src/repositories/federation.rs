@@ -0,0 +1,170 @@
+use rsa::RsaPrivateKey;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::federation::{self, ActorKeypair};
+use crate::models::user::ProfileData;
+
+pub struct FederationRepository {
+    pool: PgPool,
+}
+
+impl FederationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Return the user's actor keypair, generating and persisting one on first use. Returns
+    /// the public key PEM plus the opened private key, ready to sign an outgoing activity.
+    pub async fn ensure_keypair(&self, user_id: Uuid) -> Result<(String, RsaPrivateKey), AppError> {
+        let row = sqlx::query!(
+            "SELECT public_key_pem, private_key_sealed FROM users WHERE id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let encryption_key = federation::encryption_key_from_env();
+
+        if let (Some(public_key_pem), Some(sealed)) = (row.public_key_pem, row.private_key_sealed) {
+            let private_key = federation::open_private_key(&sealed, &encryption_key)
+                .ok_or(AppError::Internal)?;
+            return Ok((public_key_pem, private_key));
+        }
+
+        let keypair = ActorKeypair::generate();
+        let sealed = keypair.seal_private_key(&encryption_key);
+
+        sqlx::query!(
+            "UPDATE users SET public_key_pem = $2, private_key_sealed = $3 WHERE id = $1",
+            user_id,
+            keypair.public_key_pem,
+            sealed
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let private_key = federation::open_private_key(&sealed, &encryption_key)
+            .ok_or(AppError::Internal)?;
+
+        Ok((keypair.public_key_pem, private_key))
+    }
+
+    /// The public key PEM for an actor, if one has ever been generated for them.
+    pub async fn get_public_key(&self, user_id: Uuid) -> Result<Option<String>, AppError> {
+        let row = sqlx::query!("SELECT public_key_pem FROM users WHERE id = $1", user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.and_then(|r| r.public_key_pem))
+    }
+
+    /// Record a remote actor's `Follow` as pending until `accept_follower` confirms it.
+    pub async fn add_follower(&self, owner_user_id: Uuid, follower_actor_url: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO federation_followers (owner_user_id, follower_actor_url, status)
+            VALUES ($1, $2, 'pending')
+            ON CONFLICT (owner_user_id, follower_actor_url) DO NOTHING
+            "#,
+            owner_user_id,
+            follower_actor_url
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark a pending follower accepted.
+    pub async fn accept_follower(&self, owner_user_id: Uuid, follower_actor_url: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE federation_followers
+            SET status = 'accepted', updated_at = NOW()
+            WHERE owner_user_id = $1 AND follower_actor_url = $2
+            "#,
+            owner_user_id,
+            follower_actor_url
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Remove a follower, e.g. on an incoming `Undo(Follow)`.
+    pub async fn remove_follower(&self, owner_user_id: Uuid, follower_actor_url: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            "DELETE FROM federation_followers WHERE owner_user_id = $1 AND follower_actor_url = $2",
+            owner_user_id,
+            follower_actor_url
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Actor URLs of this owner's accepted followers.
+    pub async fn list_accepted_followers(&self, owner_user_id: Uuid) -> Result<Vec<String>, AppError> {
+        let rows = sqlx::query!(
+            "SELECT follower_actor_url FROM federation_followers WHERE owner_user_id = $1 AND status = 'accepted'",
+            owner_user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.follower_actor_url).collect())
+    }
+
+    /// Append a pre-built activity to the user's durable outbox. The activity is stored as
+    /// given; nothing here signs it.
+    pub async fn append_outbox(&self, user_id: Uuid, activity: &serde_json::Value) -> Result<(), AppError> {
+        sqlx::query!(
+            "INSERT INTO federation_outbox (user_id, activity_json) VALUES ($1, $2)",
+            user_id,
+            activity
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The user's outbox, most recent first.
+    pub async fn list_outbox(&self, user_id: Uuid) -> Result<Vec<serde_json::Value>, AppError> {
+        let rows = sqlx::query!(
+            "SELECT activity_json FROM federation_outbox WHERE user_id = $1 ORDER BY created_at DESC",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.activity_json).collect())
+    }
+
+    /// Build an `Ascent` `Create` activity for a successful attempt and append it to the
+    /// user's outbox, but only when `profile.privacy_settings.history_visibility` is
+    /// `"public"` — federation must respect the same visibility tier the local history view
+    /// does, not bypass it. The actor keypair is created if missing, but the activity itself
+    /// is stored unsigned.
+    pub async fn maybe_record_ascent(
+        &self,
+        user_id: Uuid,
+        username: &str,
+        profile: &ProfileData,
+        base_url: &str,
+        grade: &str,
+        problem_name: Option<&str>,
+    ) -> Result<(), AppError> {
+        if profile.privacy_settings.history_visibility != "public" {
+            return Ok(());
+        }
+
+        let (_public_key_pem, _private_key) = self.ensure_keypair(user_id).await?;
+        let actor_url = federation::actor_url(base_url, username);
+        let activity = federation::ascent_activity(base_url, &actor_url, grade, problem_name);
+
+        let activity_json = serde_json::to_value(&activity)?;
+        self.append_outbox(user_id, &activity_json).await
+    }
+}
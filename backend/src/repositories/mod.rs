@@ -0,0 +1,6 @@
+pub mod federation;
+pub mod refresh_token;
+pub mod relationship;
+pub mod totp_recovery_code;
+pub mod user;
+pub mod verification_otp;
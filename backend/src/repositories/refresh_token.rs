@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// A stored refresh token row. Only the hash of the opaque token is ever persisted.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub family_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub revoked: bool,
+}
+
+pub struct RefreshTokenRepository {
+    pool: PgPool,
+}
+
+impl RefreshTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Persist a newly issued refresh token.
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        family_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<RefreshToken, AppError> {
+        let row = sqlx::query_as!(
+            RefreshToken,
+            r#"
+            INSERT INTO refresh_tokens (user_id, token_hash, family_id, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, user_id, token_hash, family_id, expires_at, revoked
+            "#,
+            user_id,
+            token_hash,
+            family_id,
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    /// Look up a refresh token by the hash of its presented plaintext value.
+    pub async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError> {
+        let row = sqlx::query_as!(
+            RefreshToken,
+            r#"
+            SELECT id, user_id, token_hash, family_id, expires_at, revoked
+            FROM refresh_tokens
+            WHERE token_hash = $1
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    /// Mark a single token revoked (used once it has been rotated).
+    pub async fn revoke(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query!("UPDATE refresh_tokens SET revoked = TRUE WHERE id = $1", id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Revoke every token in a family. Called when a revoked token is presented again,
+    /// which signals that the family has been stolen and the whole chain must die.
+    pub async fn revoke_family(&self, family_id: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE refresh_tokens SET revoked = TRUE WHERE family_id = $1",
+            family_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Revoke every outstanding token for a user, regardless of family (used on logout-all
+    /// and account deletion).
+    pub async fn revoke_all_for_user(&self, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE refresh_tokens SET revoked = TRUE WHERE user_id = $1",
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::relationship::{Relationship, RelationshipStatus};
+
+pub struct RelationshipRepository {
+    pool: PgPool,
+}
+
+impl RelationshipRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Send a friend/follow request from `requester_id` to `addressee_id`.
+    pub async fn send_request(
+        &self,
+        requester_id: Uuid,
+        addressee_id: Uuid,
+    ) -> Result<(), AppError> {
+        if requester_id == addressee_id {
+            return Err(AppError::Validation("Cannot friend yourself".to_string()));
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO relationships (requester_id, addressee_id, status)
+            VALUES ($1, $2, 'pending')
+            ON CONFLICT (requester_id, addressee_id)
+            DO UPDATE SET status = 'pending', updated_at = NOW()
+            "#,
+            requester_id,
+            addressee_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Accept a pending request that was sent *to* `addressee_id` *by* `requester_id`.
+    pub async fn accept(&self, requester_id: Uuid, addressee_id: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE relationships
+            SET status = 'accepted', updated_at = NOW()
+            WHERE requester_id = $1 AND addressee_id = $2 AND status = 'pending'
+            "#,
+            requester_id,
+            addressee_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Reject (delete) a pending request sent *to* `addressee_id` *by* `requester_id`.
+    pub async fn reject(&self, requester_id: Uuid, addressee_id: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "DELETE FROM relationships WHERE requester_id = $1 AND addressee_id = $2 AND status = 'pending'",
+            requester_id,
+            addressee_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove an existing relationship in either direction.
+    pub async fn remove(&self, a: Uuid, b: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            DELETE FROM relationships
+            WHERE (requester_id = $1 AND addressee_id = $2)
+               OR (requester_id = $2 AND addressee_id = $1)
+            "#,
+            a,
+            b
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Look up the relationship between two users, in whichever direction it was created.
+    pub async fn find_between(&self, a: Uuid, b: Uuid) -> Result<Option<Relationship>, AppError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT requester_id, addressee_id, status, created_at, updated_at
+            FROM relationships
+            WHERE (requester_id = $1 AND addressee_id = $2)
+               OR (requester_id = $2 AND addressee_id = $1)
+            "#,
+            a,
+            b
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| Relationship {
+            requester_id: r.requester_id,
+            addressee_id: r.addressee_id,
+            status: RelationshipStatus::from_str(&r.status).unwrap_or(RelationshipStatus::Pending),
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }))
+    }
+
+    /// Whether two users have an accepted relationship in either direction.
+    pub async fn are_friends(&self, a: Uuid, b: Uuid) -> Result<bool, AppError> {
+        Ok(self
+            .find_between(a, b)
+            .await?
+            .is_some_and(|r| r.status == RelationshipStatus::Accepted))
+    }
+}
@@ -0,0 +1,61 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+pub struct TotpRecoveryCodeRepository {
+    pool: PgPool,
+}
+
+impl TotpRecoveryCodeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Replace a user's recovery codes with a freshly generated set (called on 2FA enable).
+    pub async fn replace_all(&self, user_id: Uuid, code_hashes: &[String]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!("DELETE FROM totp_recovery_codes WHERE user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for code_hash in code_hashes {
+            sqlx::query!(
+                "INSERT INTO totp_recovery_codes (user_id, code_hash) VALUES ($1, $2)",
+                user_id,
+                code_hash
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Consume a recovery code if it exists and hasn't been used yet, returning whether it
+    /// was accepted.
+    pub async fn consume(&self, user_id: Uuid, code_hash: &str) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE totp_recovery_codes
+            SET used_at = NOW()
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+            user_id,
+            code_hash
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_all(&self, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query!("DELETE FROM totp_recovery_codes WHERE user_id = $1", user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,9 +1,37 @@
 use sqlx::PgPool;
 use uuid::Uuid;
-use chrono::Utc;
-use crate::models::user::{User, UserProfile, UserStatistics, ProfileData};
+use chrono::{DateTime, Utc};
+use crate::models::user::{
+    AccountStatus, AccountStatusError, RecordAttemptError, User, UserProfile, UserStatistics,
+    ProfileData, SOFT_DELETE_RETENTION_DAYS,
+};
 use crate::error::AppError;
 
+/// Parse the `status` column, falling back to `Active` for a row written before this column
+/// existed or by any process that doesn't yet know about the lifecycle state machine.
+fn parse_status(status: &str) -> AccountStatus {
+    AccountStatus::from_str(status).unwrap_or(AccountStatus::Active)
+}
+
+impl From<RecordAttemptError> for AppError {
+    fn from(err: RecordAttemptError) -> Self {
+        let message = err.to_string();
+        match err {
+            RecordAttemptError::UnrecognizedGrade(_) => AppError::Validation(message),
+            RecordAttemptError::Serialization(e) => AppError::Json(e),
+        }
+    }
+}
+
+/// An attempted `AccountStatus` transition that the current status doesn't allow (e.g.
+/// reactivating a soft-deleted account) is a conflict with the account's existing state, not a
+/// malformed request.
+impl From<AccountStatusError> for AppError {
+    fn from(err: AccountStatusError) -> Self {
+        AppError::Conflict(err.to_string())
+    }
+}
+
 pub struct UserRepository {
     pool: PgPool,
 }
@@ -14,18 +42,29 @@ impl UserRepository {
     }
 
     /// Create a new user with initial profile and statistics
-    pub async fn create_user(&self, email: String, username: String, profile_data: Option<ProfileData>) -> Result<User, AppError> {
+    pub async fn create_user(
+        &self,
+        email: String,
+        username: String,
+        password_hash: String,
+        name: Option<String>,
+        avatar: Option<String>,
+        profile_data: Option<ProfileData>,
+    ) -> Result<User, AppError> {
         let mut tx = self.pool.begin().await?;
-        
+
         // Create the user
         let row = sqlx::query!(
             r#"
-            INSERT INTO users (email, username)
-            VALUES ($1, $2)
-            RETURNING id, email, username, created_at, updated_at
+            INSERT INTO users (email, username, password_hash, name, avatar)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, email, username, blocked, verified, status, deleted_at, name, avatar, is_staff, created_at, updated_at
             "#,
             email,
-            username
+            username,
+            password_hash,
+            name,
+            avatar
         )
         .fetch_one(&mut *tx)
         .await?;
@@ -34,6 +73,13 @@ impl UserRepository {
             id: row.id,
             email: row.email,
             username: row.username,
+            blocked: row.blocked,
+            verified: row.verified,
+            status: parse_status(&row.status),
+            deleted_at: row.deleted_at,
+            name: row.name,
+            avatar: row.avatar,
+            is_staff: row.is_staff,
             created_at: row.created_at.unwrap_or_else(|| Utc::now()),
             updated_at: row.updated_at.unwrap_or_else(|| Utc::now()),
         };
@@ -76,7 +122,7 @@ impl UserRepository {
     /// Find user by ID
     pub async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, AppError> {
         let row = sqlx::query!(
-            "SELECT id, email, username, created_at, updated_at FROM users WHERE id = $1",
+            "SELECT id, email, username, blocked, verified, status, deleted_at, name, avatar, is_staff, created_at, updated_at FROM users WHERE id = $1",
             user_id
         )
         .fetch_optional(&self.pool)
@@ -86,6 +132,13 @@ impl UserRepository {
             id: r.id,
             email: r.email,
             username: r.username,
+            blocked: r.blocked,
+            verified: r.verified,
+            status: parse_status(&r.status),
+            deleted_at: r.deleted_at,
+            name: r.name,
+            avatar: r.avatar,
+            is_staff: r.is_staff,
             created_at: r.created_at.unwrap_or_else(|| Utc::now()),
             updated_at: r.updated_at.unwrap_or_else(|| Utc::now()),
         }))
@@ -94,7 +147,7 @@ impl UserRepository {
     /// Find user by email
     pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
         let row = sqlx::query!(
-            "SELECT id, email, username, created_at, updated_at FROM users WHERE email = $1",
+            "SELECT id, email, username, blocked, verified, status, deleted_at, name, avatar, is_staff, created_at, updated_at FROM users WHERE email = $1",
             email
         )
         .fetch_optional(&self.pool)
@@ -104,6 +157,13 @@ impl UserRepository {
             id: r.id,
             email: r.email,
             username: r.username,
+            blocked: r.blocked,
+            verified: r.verified,
+            status: parse_status(&r.status),
+            deleted_at: r.deleted_at,
+            name: r.name,
+            avatar: r.avatar,
+            is_staff: r.is_staff,
             created_at: r.created_at.unwrap_or_else(|| Utc::now()),
             updated_at: r.updated_at.unwrap_or_else(|| Utc::now()),
         }))
@@ -112,7 +172,7 @@ impl UserRepository {
     /// Find user by username
     pub async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError> {
         let row = sqlx::query!(
-            "SELECT id, email, username, created_at, updated_at FROM users WHERE username = $1",
+            "SELECT id, email, username, blocked, verified, status, deleted_at, name, avatar, is_staff, created_at, updated_at FROM users WHERE username = $1",
             username
         )
         .fetch_optional(&self.pool)
@@ -122,11 +182,259 @@ impl UserRepository {
             id: r.id,
             email: r.email,
             username: r.username,
+            blocked: r.blocked,
+            verified: r.verified,
+            status: parse_status(&r.status),
+            deleted_at: r.deleted_at,
+            name: r.name,
+            avatar: r.avatar,
+            is_staff: r.is_staff,
             created_at: r.created_at.unwrap_or_else(|| Utc::now()),
             updated_at: r.updated_at.unwrap_or_else(|| Utc::now()),
         }))
     }
 
+    /// Check whether a user has the staff role, for the `StaffUser` extractor gating
+    /// admin-only operations.
+    pub async fn is_staff(&self, user_id: Uuid) -> Result<bool, AppError> {
+        let row = sqlx::query!("SELECT is_staff FROM users WHERE id = $1", user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|r| r.is_staff).unwrap_or(false))
+    }
+
+    /// Every user account, oldest first, `limit` rows starting at `offset`. Staff-only —
+    /// callers should gate on `StaffUser` rather than exposing this to ordinary users.
+    pub async fn list_users(&self, limit: i64, offset: i64) -> Result<Vec<User>, AppError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, email, username, blocked, verified, status, deleted_at, name, avatar, is_staff, created_at, updated_at
+            FROM users
+            ORDER BY created_at
+            LIMIT $1 OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| User {
+                id: r.id,
+                email: r.email,
+                username: r.username,
+                blocked: r.blocked,
+                verified: r.verified,
+                status: parse_status(&r.status),
+                deleted_at: r.deleted_at,
+                name: r.name,
+                avatar: r.avatar,
+                is_staff: r.is_staff,
+                created_at: r.created_at.unwrap_or_else(|| Utc::now()),
+                updated_at: r.updated_at.unwrap_or_else(|| Utc::now()),
+            })
+            .collect())
+    }
+
+    /// Check whether a user is currently blocked, for the per-request auth check. Callers
+    /// should go through `state::BlockedUserCache` rather than calling this directly on
+    /// every request.
+    pub async fn is_blocked(&self, user_id: Uuid) -> Result<bool, AppError> {
+        let row = sqlx::query!("SELECT blocked FROM users WHERE id = $1", user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|r| r.blocked).unwrap_or(false))
+    }
+
+    /// Set or clear a user's blocked status, e.g. from an admin endpoint. Callers must also
+    /// invalidate the corresponding `BlockedUserCache` entry so the change takes effect
+    /// immediately rather than after its TTL expires.
+    pub async fn set_blocked(&self, user_id: Uuid, blocked: bool) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET blocked = $2, updated_at = NOW() WHERE id = $1",
+            user_id,
+            blocked
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Check whether a user has proven ownership of their email via an `EmailVerify` OTP.
+    /// Sensitive operations that require a verified account should call this instead of
+    /// assuming registration alone proves ownership.
+    pub async fn is_verified(&self, user_id: Uuid) -> Result<bool, AppError> {
+        let row = sqlx::query!("SELECT verified FROM users WHERE id = $1", user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|r| r.verified).unwrap_or(false))
+    }
+
+    /// Mark a user's email as verified, called once a `VerificationOtp` for `EmailVerify` is
+    /// successfully consumed.
+    pub async fn set_verified(&self, user_id: Uuid, verified: bool) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET verified = $2, updated_at = NOW() WHERE id = $1",
+            user_id,
+            verified
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Persist an `AccountStatus` transition produced by `User::suspend`/`deactivate`/
+    /// `reactivate` (anything other than `soft_delete`, which also needs to clear/set
+    /// `deleted_at` via `soft_delete_user`).
+    pub async fn set_status(&self, user_id: Uuid, status: AccountStatus) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET status = $2, updated_at = NOW() WHERE id = $1",
+            user_id,
+            status.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Soft-delete a user: flips `status` to `soft_deleted` and stamps `deleted_at`, starting
+    /// the retention window `find_purgeable` checks against, rather than removing the row.
+    pub async fn soft_delete_user(&self, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET status = $2, deleted_at = NOW(), updated_at = NOW() WHERE id = $1",
+            user_id,
+            AccountStatus::SoftDeleted.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// IDs of soft-deleted accounts whose retention window has elapsed as of `now`, eligible
+    /// for `delete_user` to hard-purge.
+    pub async fn find_purgeable(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>, AppError> {
+        let cutoff = now - chrono::Duration::days(SOFT_DELETE_RETENTION_DAYS);
+        let rows = sqlx::query!(
+            "SELECT id FROM users WHERE status = $1 AND deleted_at <= $2",
+            AccountStatus::SoftDeleted.as_str(),
+            cutoff
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.id).collect())
+    }
+
+    /// Look up a user's stored password hash by email, for the login flow.
+    pub async fn find_password_hash_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Option<(Uuid, String)>, AppError> {
+        let row = sqlx::query!(
+            "SELECT id, password_hash FROM users WHERE email = $1",
+            email
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| (r.id, r.password_hash)))
+    }
+
+    /// Authenticate by email + plaintext password. Returns `AppError::Auth` on any mismatch
+    /// (unknown email or wrong password) rather than distinguishing the two, so a failed
+    /// login doesn't leak which part was wrong.
+    pub async fn authenticate(&self, email: &str, plaintext: &str) -> Result<User, AppError> {
+        let (user_id, password_hash) = self
+            .find_password_hash_by_email(email)
+            .await?
+            .ok_or_else(|| AppError::Auth("Invalid email or password".to_string()))?;
+
+        if !crate::auth::password::verify_password(plaintext, &password_hash)? {
+            return Err(AppError::Auth("Invalid email or password".to_string()));
+        }
+
+        self.find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::Auth("Invalid email or password".to_string()))
+    }
+
+    /// Look up a user's stored password hash by id, for the change-password flow.
+    pub async fn find_password_hash_by_id(&self, user_id: Uuid) -> Result<Option<String>, AppError> {
+        let row = sqlx::query!(
+            "SELECT password_hash FROM users WHERE id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| r.password_hash))
+    }
+
+    /// Update a user's password hash.
+    pub async fn update_password_hash(&self, user_id: Uuid, password_hash: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET password_hash = $2, updated_at = NOW() WHERE id = $1",
+            user_id,
+            password_hash
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fetch the sealed TOTP secret and enabled flag for a user, if 2FA has ever been set up.
+    pub async fn get_totp_state(&self, user_id: Uuid) -> Result<Option<(String, bool)>, AppError> {
+        let row = sqlx::query!(
+            "SELECT totp_secret, totp_enabled FROM users WHERE id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.and_then(|r| r.totp_secret.map(|secret| (secret, r.totp_enabled))))
+    }
+
+    /// Store a newly-enrolled (but not yet enabled) sealed TOTP secret.
+    pub async fn set_totp_secret(&self, user_id: Uuid, sealed_secret: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET totp_secret = $2 WHERE id = $1",
+            user_id,
+            sealed_secret
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Flip `totp_enabled`, required after the user proves they can generate a valid code.
+    pub async fn set_totp_enabled(&self, user_id: Uuid, enabled: bool) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET totp_enabled = $2 WHERE id = $1",
+            user_id,
+            enabled
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Remove 2FA entirely (secret and enabled flag).
+    pub async fn clear_totp(&self, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET totp_secret = NULL, totp_enabled = FALSE WHERE id = $1",
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Get user profile
     pub async fn get_profile(&self, user_id: Uuid) -> Result<Option<UserProfile>, AppError> {
         let row = sqlx::query!(
@@ -197,7 +505,7 @@ impl UserRepository {
             .ok_or_else(|| AppError::NotFound("User statistics not found".to_string()))?;
 
         // Update the statistics
-        statistics.record_attempt(grade, success)?;
+        statistics.record_attempt(grade, success, Utc::now(), None)?;
 
         // Save back to database
         let row = sqlx::query!(
@@ -228,7 +536,10 @@ impl UserRepository {
         })
     }
 
-    /// Delete user and all related data
+    /// Hard-delete a user and all related data. This is the purge step of the lifecycle, not
+    /// the user-facing "delete my account" action — callers should go through
+    /// `soft_delete_user` (and `find_purgeable` once the retention window elapses) instead of
+    /// calling this directly on an account that hasn't already been soft-deleted.
     pub async fn delete_user(&self, user_id: Uuid) -> Result<(), AppError> {
         let mut tx = self.pool.begin().await?;
 
@@ -0,0 +1,81 @@
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::verification::{expiry_from, generate_code, hash_code, VerificationPurpose};
+
+pub struct VerificationOtpRepository {
+    pool: PgPool,
+}
+
+impl VerificationOtpRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Mint a fresh OTP for `purpose`, invalidating any still-unused code already issued to
+    /// this user for the same purpose so only the most recently sent code can be consumed.
+    /// Returns the plaintext code to be emailed once; only its hash is persisted.
+    pub async fn issue(
+        &self,
+        user_id: Uuid,
+        purpose: VerificationPurpose,
+    ) -> Result<String, AppError> {
+        let code = generate_code();
+        let now = Utc::now();
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "UPDATE verification_otps SET used_at = $3 WHERE user_id = $1 AND purpose = $2 AND used_at IS NULL",
+            user_id,
+            purpose.as_str(),
+            now,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO verification_otps (user_id, purpose, code_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            user_id,
+            purpose.as_str(),
+            hash_code(&code),
+            expiry_from(now),
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(code)
+    }
+
+    /// Consume a presented code for `purpose` if it matches an unused, unexpired row,
+    /// returning whether it was accepted. A stale or already-consumed code is rejected rather
+    /// than silently accepted.
+    pub async fn consume(
+        &self,
+        user_id: Uuid,
+        purpose: VerificationPurpose,
+        code: &str,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE verification_otps
+            SET used_at = NOW()
+            WHERE user_id = $1 AND purpose = $2 AND code_hash = $3
+              AND used_at IS NULL AND expires_at > NOW()
+            "#,
+            user_id,
+            purpose.as_str(),
+            hash_code(code),
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,14 +1,144 @@
+use serde::Serialize;
 use sqlx::PgPool;
-use crate::auth::JwtConfig;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+use crate::auth::{JwtConfig, OAuthConfig};
+use crate::email::VerificationEmailSender;
+
+/// Short-TTL cache of each user's blocked status, so `AuthUser` doesn't hit the database on
+/// every authenticated request. An admin toggling a user's status calls `invalidate` to make
+/// the change take effect immediately instead of waiting out the TTL.
+pub struct BlockedUserCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<Uuid, (bool, Instant)>>,
+}
+
+impl BlockedUserCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// A fresh cached value for `user_id`, or `None` on a miss/expiry.
+    pub fn get(&self, user_id: Uuid) -> Option<bool> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(&user_id).and_then(|(blocked, cached_at)| {
+            (cached_at.elapsed() < self.ttl).then_some(*blocked)
+        })
+    }
+
+    pub fn set(&self, user_id: Uuid, blocked: bool) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(user_id, (blocked, Instant::now()));
+    }
+
+    /// Drop a cached entry, e.g. right after an admin flips the user's blocked status.
+    pub fn invalidate(&self, user_id: Uuid) {
+        self.entries.lock().unwrap().remove(&user_id);
+    }
+}
+
+impl Default for BlockedUserCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(30))
+    }
+}
+
+/// Counters for how JWT verification resolves across all requests, with no secret or token
+/// material attached — safe to expose through `/health` for operators who need visibility
+/// into auth failures without reading logs.
+#[derive(Default)]
+pub struct AuthMetrics {
+    verified: AtomicU64,
+    expired: AtomicU64,
+    malformed: AtomicU64,
+    blocked: AtomicU64,
+}
+
+impl AuthMetrics {
+    pub fn record_verified(&self) {
+        self.verified.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_expired(&self) {
+        self.expired.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_malformed(&self) {
+        self.malformed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_blocked(&self) {
+        self.blocked.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> AuthMetricsSnapshot {
+        AuthMetricsSnapshot {
+            verified: self.verified.load(Ordering::Relaxed),
+            expired: self.expired.load(Ordering::Relaxed),
+            malformed: self.malformed.load(Ordering::Relaxed),
+            blocked: self.blocked.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthMetricsSnapshot {
+    pub verified: u64,
+    pub expired: u64,
+    pub malformed: u64,
+    pub blocked: u64,
+}
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
     pub jwt_config: JwtConfig,
+    pub blocked_users: Arc<BlockedUserCache>,
+    pub auth_metrics: Arc<AuthMetrics>,
+    /// `None` when this instance has no social-login provider configured; the OAuth routes
+    /// reject with `AppError::OAuth` rather than panicking when this is unset.
+    pub oauth_config: Option<Arc<OAuthConfig>>,
+    /// This instance's externally-reachable origin (e.g. `https://example.com`), used to build
+    /// ActivityPub actor/activity IDs and URLs. See `handlers::federation`.
+    pub federation_base_url: String,
+    /// Where `EmailVerify` OTPs get dispatched. Defaults to `email::LoggingEmailSender` in
+    /// `main`; swap in a real transport there for a deployment reachable by real signups.
+    pub email_sender: Arc<dyn VerificationEmailSender>,
 }
 
 impl AsRef<crate::auth::JwtConfig> for AppState {
     fn as_ref(&self) -> &crate::auth::JwtConfig {
         &self.jwt_config
     }
-}
\ No newline at end of file
+}
+
+impl AsRef<PgPool> for AppState {
+    fn as_ref(&self) -> &PgPool {
+        &self.db
+    }
+}
+
+impl AsRef<BlockedUserCache> for AppState {
+    fn as_ref(&self) -> &BlockedUserCache {
+        self.blocked_users.as_ref()
+    }
+}
+
+impl AsRef<AuthMetrics> for AppState {
+    fn as_ref(&self) -> &AuthMetrics {
+        self.auth_metrics.as_ref()
+    }
+}
@@ -1,6 +1,12 @@
+use chrono::Utc;
 use proptest::prelude::*;
 use uuid::Uuid;
-use crate::models::user::{User, UserProfile, UserStatistics, ProfileData, PrivacySettings};
+use crate::models::relationship::ViewerRelation;
+use crate::models::user::{
+    AccountStatus, User, UserProfile, UserStatistics, ProfileData, PrivacySettings,
+    SOFT_DELETE_RETENTION_DAYS,
+};
+use crate::models::verification::{hash_code, VerificationOtp, VerificationPurpose};
 
 /// Property Test 1: User Account Lifecycle Consistency
 /// 
@@ -49,6 +55,7 @@ mod user_account_lifecycle {
                 avatar_url,
                 location,
                 preferred_units,
+                preferred_grading_system: None,
                 privacy_settings,
             }
         })
@@ -69,9 +76,22 @@ mod user_account_lifecycle {
         })
     }
 
-    // Strategy for generating climbing grades
+    // Strategy for generating climbing grades across the supported scales (V-scale, Font,
+    // YDS), so lifecycle/progression properties don't only ever exercise the V-scale path.
     fn grade_strategy() -> impl Strategy<Value = String> {
-        (0..18u8).prop_map(|n| format!("V{}", n))
+        prop_oneof![
+            (0..18u8).prop_map(|n| format!("V{}", n)),
+            prop::sample::select(vec![
+                "4", "5", "5+", "6A", "6A+", "6B", "6B+", "6C", "6C+", "7A", "7A+", "7B", "7B+",
+                "7C", "7C+", "8A", "8A+", "8B",
+            ])
+            .prop_map(|s| s.to_string()),
+            prop::sample::select(vec![
+                "5.8", "5.9", "5.10a", "5.10b", "5.10c", "5.10d", "5.11a", "5.11b", "5.11c",
+                "5.11d", "5.12a", "5.12b", "5.12c", "5.12d", "5.13a", "5.13b", "5.13c", "5.13d",
+            ])
+            .prop_map(|s| s.to_string()),
+        ]
     }
 
     proptest! {
@@ -88,7 +108,8 @@ mod user_account_lifecycle {
             attempts in prop::collection::vec((grade_strategy(), any::<bool>()), 0..20)
         ) {
             // Property 1.1: User creation produces valid users
-            let user = User::new(email.clone(), username.clone());
+            let user = User::new(email.clone(), username.clone())
+                .expect("email_strategy produces RFC-shaped addresses");
             
             // User should have valid UUID
             prop_assert!(!user.id.is_nil());
@@ -96,13 +117,53 @@ mod user_account_lifecycle {
             // User should preserve input data
             prop_assert_eq!(user.email, email);
             prop_assert_eq!(user.username, username);
-            
+
             // Timestamps should be reasonable (within last minute)
             let now = chrono::Utc::now();
             let time_diff = now.signed_duration_since(user.created_at);
             prop_assert!(time_diff.num_seconds() >= 0 && time_diff.num_seconds() < 60);
             prop_assert_eq!(user.created_at, user.updated_at);
 
+            // Property 1.1b: a fresh account starts unverified, and only transitions to
+            // verified by consuming a still-valid, not-yet-used `EmailVerify` OTP.
+            prop_assert!(!user.verified);
+
+            let code = "123456".to_string();
+            let mut otp = VerificationOtp {
+                id: Uuid::new_v4(),
+                user_id: user.id,
+                purpose: VerificationPurpose::EmailVerify,
+                code_hash: hash_code(&code),
+                expires_at: crate::models::verification::expiry_from(now),
+                used_at: None,
+                created_at: now,
+            };
+
+            // A fresh, unused, unexpired OTP is valid and flips the account to verified.
+            prop_assert!(otp.is_valid_at(now));
+            let mut verified = user.verified;
+            if otp.is_valid_at(now) {
+                verified = true;
+                otp.used_at = Some(now);
+            }
+            prop_assert!(verified);
+
+            // The same OTP cannot be consumed a second time (reuse rejected).
+            prop_assert!(!otp.is_valid_at(now));
+
+            // A code minted far enough in the past to be past its validity window is stale
+            // and rejected even though it was never consumed.
+            let stale_otp = VerificationOtp {
+                id: Uuid::new_v4(),
+                user_id: user.id,
+                purpose: VerificationPurpose::EmailVerify,
+                code_hash: hash_code(&code),
+                expires_at: now - chrono::Duration::seconds(1),
+                used_at: None,
+                created_at: now - chrono::Duration::minutes(crate::models::verification::OTP_VALIDITY_MINUTES + 1),
+            };
+            prop_assert!(!stale_otp.is_valid_at(now));
+
             // Property 1.2: Profile creation and updates maintain consistency
             let mut profile = UserProfile::new(user.id, Some(initial_profile.clone()));
             
@@ -140,7 +201,7 @@ mod user_account_lifecycle {
             let mut grade_distribution: HashMap<String, i32> = HashMap::new();
             
             for (grade, success) in attempts {
-                statistics.record_attempt(&grade, success).unwrap();
+                statistics.record_attempt(&grade, success, Utc::now(), None).unwrap();
                 expected_attempts += 1;
                 
                 if success {
@@ -152,8 +213,13 @@ mod user_account_lifecycle {
                     }
                 }
                 
-                // Update expected grade distribution
-                *grade_distribution.entry(grade).or_insert(0) += 1;
+                // Update expected grade distribution, keyed by canonical V-scale grade to
+                // mirror `UserStatistics::record_attempt`'s cross-scale deduplication.
+                let canonical_grade = crate::models::grading::format_ordinal(
+                    crate::models::grading::parse_grade(&grade).unwrap().ordinal,
+                    crate::models::grading::GradingSystem::VScale,
+                );
+                *grade_distribution.entry(canonical_grade).or_insert(0) += 1;
                 
                 // Verify statistics consistency after each attempt
                 prop_assert_eq!(statistics.total_attempts, expected_attempts);
@@ -174,6 +240,53 @@ mod user_account_lifecycle {
             
             // Timestamps should be monotonic (newer operations have later timestamps)
             prop_assert!(statistics.updated_at >= user.created_at);
+
+            // Property 1.5: Account lifecycle covers the full cradle-to-grave path, with
+            // state invariants holding at every step: creation → deactivation →
+            // reactivation → suspension → soft-delete → (eventually) purge.
+            let mut lifecycle_user = user.clone();
+            prop_assert_eq!(lifecycle_user.status, AccountStatus::Active);
+
+            lifecycle_user.deactivate().unwrap();
+            prop_assert_eq!(lifecycle_user.status, AccountStatus::Deactivated);
+            // The Uuid and creation timestamp never change across a transition.
+            prop_assert_eq!(lifecycle_user.id, user.id);
+            prop_assert_eq!(lifecycle_user.created_at, user.created_at);
+            // A deactivated account can't be deactivated again.
+            prop_assert!(lifecycle_user.deactivate().is_err());
+
+            lifecycle_user.reactivate().unwrap();
+            prop_assert_eq!(lifecycle_user.status, AccountStatus::Active);
+
+            lifecycle_user.suspend().unwrap();
+            prop_assert_eq!(lifecycle_user.status, AccountStatus::Suspended);
+            lifecycle_user.reactivate().unwrap();
+            prop_assert_eq!(lifecycle_user.status, AccountStatus::Active);
+
+            // export_data is available right up until soft-delete, and round-trips the
+            // user's own id.
+            let bundle = lifecycle_user.export_data(&profile, &statistics).unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&bundle).unwrap();
+            prop_assert_eq!(
+                parsed["user"]["id"].as_str().unwrap(),
+                lifecycle_user.id.to_string()
+            );
+
+            lifecycle_user.soft_delete().unwrap();
+            prop_assert_eq!(lifecycle_user.status, AccountStatus::SoftDeleted);
+            prop_assert!(lifecycle_user.deleted_at.is_some());
+            // Soft-deletion is not immediately purgeable...
+            prop_assert!(!lifecycle_user.can_purge(lifecycle_user.deleted_at.unwrap()));
+            // ...but becomes so once the retention window has elapsed.
+            let past_retention = lifecycle_user.deleted_at.unwrap()
+                + chrono::Duration::days(SOFT_DELETE_RETENTION_DAYS)
+                + chrono::Duration::seconds(1);
+            prop_assert!(lifecycle_user.can_purge(past_retention));
+            // Monotonic: a soft-deleted account can no longer be reactivated or re-deleted —
+            // the only way forward from here is a hard purge, which removes the row entirely
+            // rather than transitioning `status` again.
+            prop_assert!(lifecycle_user.reactivate().is_err());
+            prop_assert!(lifecycle_user.soft_delete().is_err());
         }
 
         /// Property 2: Profile Privacy Settings Consistency
@@ -228,7 +341,7 @@ mod user_account_lifecycle {
             
             for (grade, success) in attempts {
                 let old_best = statistics.personal_best_grade.clone();
-                statistics.record_attempt(&grade, success).unwrap();
+                statistics.record_attempt(&grade, success, Utc::now(), None).unwrap();
                 
                 if success {
                     // Update our tracking of max grade
@@ -258,11 +371,13 @@ mod user_account_lifecycle {
         }
     }
 
-    // Helper function to compare grades (V0 < V1 < V2 ... < V17)
+    // Helper function to compare grades by their cross-scale difficulty ordinal, mirroring
+    // `UserStatistics::is_harder_grade`, so this test stays correct now that `grade_strategy`
+    // generates V-scale, Font, and YDS grades rather than only V-scale.
     fn is_harder_grade(grade1: &str, grade2: &str) -> bool {
-        let num1 = grade1.trim_start_matches('V').parse::<i32>().unwrap_or(0);
-        let num2 = grade2.trim_start_matches('V').parse::<i32>().unwrap_or(0);
-        num1 > num2
+        let ordinal1 = crate::models::grading::parse_grade(grade1).map(|p| p.ordinal).unwrap_or(0);
+        let ordinal2 = crate::models::grading::parse_grade(grade2).map(|p| p.ordinal).unwrap_or(0);
+        ordinal1 > ordinal2
     }
 }
 
@@ -283,7 +398,8 @@ mod json_serialization_tests {
             profile_data in any::<ProfileData>(),
         ) {
             // Test User serialization
-            let user = User::new(email, username);
+            let user = User::new(email, username)
+                .expect("the email regex strategy above only generates valid addresses");
             let user_json = serde_json::to_string(&user).unwrap();
             let user_deserialized: User = serde_json::from_str(&user_json).unwrap();
             
@@ -310,6 +426,130 @@ mod json_serialization_tests {
     }
 }
 
+/// Property Test 5: Privacy Projection Never Leaks Past the Stored Visibility Tier
+///
+/// Tests that `ProfileData::view_as` and `UserStatistics::view_as` never reveal a field to a
+/// viewer whose relationship tier doesn't satisfy the stored visibility setting, regardless of
+/// what the underlying data happens to be.
+#[cfg(test)]
+mod privacy_projection {
+    use super::*;
+
+    fn relation_strategy() -> impl Strategy<Value = ViewerRelation> {
+        prop_oneof![
+            Just(ViewerRelation::Owner),
+            Just(ViewerRelation::Friend),
+            Just(ViewerRelation::Stranger),
+        ]
+    }
+
+    fn visibility_strategy() -> impl Strategy<Value = String> {
+        prop::string::string_regex(r"(public|friends|private)").unwrap()
+    }
+
+    // A profile with every redactable field populated, so a leaked field always shows up.
+    fn populated_profile(visibility: &str) -> ProfileData {
+        ProfileData {
+            first_name: Some("Alex".to_string()),
+            last_name: Some("Honnold".to_string()),
+            display_name: Some("Climber".to_string()),
+            bio: Some("Free solos on weekends.".to_string()),
+            avatar_url: Some("https://example.com/avatar.png".to_string()),
+            location: Some("Yosemite".to_string()),
+            preferred_units: Some("metric".to_string()),
+            preferred_grading_system: None,
+            privacy_settings: PrivacySettings {
+                profile_visibility: visibility.to_string(),
+                statistics_visibility: visibility.to_string(),
+                history_visibility: visibility.to_string(),
+            },
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn profile_projection_respects_visibility_tier(
+            visibility in visibility_strategy(),
+            relation in relation_strategy(),
+        ) {
+            let profile = populated_profile(&visibility);
+            let projected = profile.view_as(relation);
+
+            let entitled = relation == ViewerRelation::Owner || relation.satisfies(&visibility);
+            let reduced_to_friends = visibility == "friends" && relation == ViewerRelation::Friend;
+
+            if relation == ViewerRelation::Owner {
+                // The owner always sees their own data untouched.
+                prop_assert_eq!(projected.first_name, profile.first_name);
+                prop_assert_eq!(projected.bio, profile.bio);
+            } else if reduced_to_friends {
+                // Friends see a reduced field set, never the fully private fields.
+                prop_assert_eq!(&projected.first_name, &None);
+                prop_assert_eq!(&projected.last_name, &None);
+                prop_assert_eq!(&projected.bio, &None);
+                prop_assert_eq!(&projected.location, &None);
+                prop_assert_eq!(&projected.preferred_units, &None);
+                prop_assert_eq!(projected.display_name, profile.display_name);
+            } else if entitled {
+                prop_assert_eq!(projected.first_name, profile.first_name);
+                prop_assert_eq!(projected.bio, profile.bio);
+            } else {
+                // Below the required tier: no redactable field may leak.
+                prop_assert_eq!(&projected.first_name, &None);
+                prop_assert_eq!(&projected.last_name, &None);
+                prop_assert_eq!(&projected.bio, &None);
+                prop_assert_eq!(&projected.location, &None);
+                prop_assert_eq!(&projected.avatar_url, &None);
+                prop_assert_eq!(&projected.preferred_units, &None);
+            }
+        }
+
+        #[test]
+        fn statistics_projection_respects_visibility_tier(
+            visibility in visibility_strategy(),
+            relation in relation_strategy(),
+            grade in (0..18u8).prop_map(|n| format!("V{}", n)),
+        ) {
+            let user_id = Uuid::new_v4();
+            let mut statistics = UserStatistics::new(user_id);
+            statistics.record_attempt(&grade, true, Utc::now(), None).unwrap();
+
+            let projected = statistics.view_as(relation, &visibility, &visibility, None).unwrap();
+
+            if relation.satisfies(&visibility) {
+                prop_assert_eq!(projected.total_attempts, Some(statistics.total_attempts));
+                prop_assert_eq!(projected.total_ascents, Some(statistics.total_ascents));
+                prop_assert!(projected.grade_distribution.is_some());
+                prop_assert!(projected.attempt_history.is_some());
+            } else {
+                prop_assert_eq!(&projected.total_attempts, &None);
+                prop_assert_eq!(&projected.total_ascents, &None);
+                prop_assert_eq!(&projected.personal_best_grade, &None);
+                prop_assert_eq!(&projected.grade_distribution, &None);
+                prop_assert_eq!(&projected.attempt_history, &None);
+            }
+        }
+
+        #[test]
+        fn statistics_history_visibility_is_gated_independently_of_statistics_visibility(
+            history_visibility in visibility_strategy(),
+            relation in relation_strategy(),
+            grade in (0..18u8).prop_map(|n| format!("V{}", n)),
+        ) {
+            let user_id = Uuid::new_v4();
+            let mut statistics = UserStatistics::new(user_id);
+            statistics.record_attempt(&grade, true, Utc::now(), None).unwrap();
+
+            // Aggregates are always public here, so only `history_visibility` decides whether
+            // `attempt_history` comes back.
+            let projected = statistics.view_as(relation, "public", &history_visibility, None).unwrap();
+
+            prop_assert!(projected.total_attempts.is_some());
+            prop_assert_eq!(projected.attempt_history.is_some(), relation.satisfies(&history_visibility));
+        }
+    }
+}
+
 // Custom strategy implementations for complex types
 impl Arbitrary for ProfileData {
     type Parameters = ();
@@ -334,6 +574,7 @@ impl Arbitrary for ProfileData {
                 avatar_url,
                 location,
                 preferred_units,
+                preferred_grading_system: None,
                 privacy_settings,
             }
         }).boxed()
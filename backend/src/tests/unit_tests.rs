@@ -11,7 +11,7 @@ mod user_model_tests {
         let email = "test@example.com".to_string();
         let username = "testuser".to_string();
         
-        let user = User::new(email.clone(), username.clone());
+        let user = User::new(email.clone(), username.clone()).unwrap();
         
         assert_eq!(user.email, email);
         assert_eq!(user.username, username);
@@ -50,6 +50,7 @@ mod user_model_tests {
             avatar_url: Some("https://example.com/avatar.jpg".to_string()),
             location: Some("Boulder, CO".to_string()),
             preferred_units: Some("imperial".to_string()),
+            preferred_grading_system: None,
             privacy_settings: PrivacySettings {
                 profile_visibility: "friends".to_string(),
                 statistics_visibility: "private".to_string(),
@@ -92,6 +93,7 @@ mod user_model_tests {
             avatar_url: None,
             location: Some("Seattle, WA".to_string()),
             preferred_units: Some("metric".to_string()),
+            preferred_grading_system: None,
             privacy_settings: PrivacySettings {
                 profile_visibility: "public".to_string(),
                 statistics_visibility: "friends".to_string(),
@@ -141,7 +143,7 @@ mod user_model_tests {
         let mut statistics = UserStatistics::new(user_id);
         
         // Record a successful attempt
-        statistics.record_attempt("V3", true).unwrap();
+        statistics.record_attempt("V3", true, Utc::now(), None).unwrap();
         
         assert_eq!(statistics.total_attempts, 1);
         assert_eq!(statistics.total_ascents, 1);
@@ -163,7 +165,7 @@ mod user_model_tests {
         let mut statistics = UserStatistics::new(user_id);
         
         // Record a failed attempt
-        statistics.record_attempt("V5", false).unwrap();
+        statistics.record_attempt("V5", false, Utc::now(), None).unwrap();
         
         assert_eq!(statistics.total_attempts, 1);
         assert_eq!(statistics.total_ascents, 0);
@@ -185,22 +187,22 @@ mod user_model_tests {
         let mut statistics = UserStatistics::new(user_id);
         
         // Record attempts in various orders
-        statistics.record_attempt("V2", true).unwrap();
+        statistics.record_attempt("V2", true, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V2".to_string()));
         
-        statistics.record_attempt("V1", true).unwrap();
+        statistics.record_attempt("V1", true, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V2".to_string())); // Should not decrease
         
-        statistics.record_attempt("V4", true).unwrap();
+        statistics.record_attempt("V4", true, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V4".to_string())); // Should increase
         
-        statistics.record_attempt("V3", true).unwrap();
+        statistics.record_attempt("V3", true, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V4".to_string())); // Should not decrease
         
-        statistics.record_attempt("V6", false).unwrap();
+        statistics.record_attempt("V6", false, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V4".to_string())); // Failed attempts don't affect PB
         
-        statistics.record_attempt("V5", true).unwrap();
+        statistics.record_attempt("V5", true, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V5".to_string())); // Should increase
         
         // Verify final statistics
@@ -222,10 +224,10 @@ mod user_model_tests {
         let mut statistics = UserStatistics::new(user_id);
         
         // Record multiple attempts on the same grade
-        statistics.record_attempt("V3", false).unwrap();
-        statistics.record_attempt("V3", false).unwrap();
-        statistics.record_attempt("V3", true).unwrap();
-        statistics.record_attempt("V3", true).unwrap();
+        statistics.record_attempt("V3", false, Utc::now(), None).unwrap();
+        statistics.record_attempt("V3", false, Utc::now(), None).unwrap();
+        statistics.record_attempt("V3", true, Utc::now(), None).unwrap();
+        statistics.record_attempt("V3", true, Utc::now(), None).unwrap();
         
         assert_eq!(statistics.total_attempts, 4);
         assert_eq!(statistics.total_ascents, 2);
@@ -241,16 +243,16 @@ mod user_model_tests {
         let mut statistics = UserStatistics::new(user_id);
         
         // Test the internal grade comparison logic through behavior
-        statistics.record_attempt("V0", true).unwrap();
+        statistics.record_attempt("V0", true, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V0".to_string()));
         
-        statistics.record_attempt("V10", true).unwrap();
+        statistics.record_attempt("V10", true, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V10".to_string()));
         
-        statistics.record_attempt("V5", true).unwrap();
+        statistics.record_attempt("V5", true, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V10".to_string())); // Should not decrease
         
-        statistics.record_attempt("V17", true).unwrap();
+        statistics.record_attempt("V17", true, Utc::now(), None).unwrap();
         assert_eq!(statistics.personal_best_grade, Some("V17".to_string()));
     }
 
@@ -260,9 +262,9 @@ mod user_model_tests {
         let mut statistics = UserStatistics::new(user_id);
         
         // Record several attempts
-        statistics.record_attempt("V2", true).unwrap();
-        statistics.record_attempt("V3", false).unwrap();
-        statistics.record_attempt("V2", true).unwrap();
+        statistics.record_attempt("V2", true, Utc::now(), None).unwrap();
+        statistics.record_attempt("V3", false, Utc::now(), None).unwrap();
+        statistics.record_attempt("V2", true, Utc::now(), None).unwrap();
         
         let stats_data = statistics.get_statistics_data().unwrap();
         assert_eq!(stats_data.monthly_progress.len(), 1);
@@ -307,8 +309,8 @@ mod user_validation_tests {
 
     #[test]
     fn test_user_id_uniqueness() {
-        let user1 = User::new("user1@example.com".to_string(), "user1".to_string());
-        let user2 = User::new("user2@example.com".to_string(), "user2".to_string());
+        let user1 = User::new("user1@example.com".to_string(), "user1".to_string()).unwrap();
+        let user2 = User::new("user2@example.com".to_string(), "user2".to_string()).unwrap();
         
         assert_ne!(user1.id, user2.id);
     }
@@ -323,6 +325,7 @@ mod user_validation_tests {
             avatar_url: Some("https://example.com/avatar.jpg".to_string()),
             location: Some("Test City, Test State".to_string()),
             preferred_units: Some("metric".to_string()),
+            preferred_grading_system: None,
             privacy_settings: PrivacySettings {
                 profile_visibility: "friends".to_string(),
                 statistics_visibility: "private".to_string(),
@@ -351,9 +354,9 @@ mod user_validation_tests {
         let mut statistics = UserStatistics::new(user_id);
         
         // Add some data
-        statistics.record_attempt("V3", true).unwrap();
-        statistics.record_attempt("V4", false).unwrap();
-        statistics.record_attempt("V5", true).unwrap();
+        statistics.record_attempt("V3", true, Utc::now(), None).unwrap();
+        statistics.record_attempt("V4", false, Utc::now(), None).unwrap();
+        statistics.record_attempt("V5", true, Utc::now(), None).unwrap();
         
         let json = serde_json::to_string(&statistics).unwrap();
         let deserialized: UserStatistics = serde_json::from_str(&json).unwrap();
@@ -12,8 +12,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let username = "testuser".to_string();
     
     println!("Creating token with config:");
-    println!("Secret: {}", config.secret);
-    println!("Algorithm: {:?}", config.algorithm);
+    println!("Secret: {}", config.primary.secret);
+    println!("Algorithm: {:?}", config.primary.algorithm);
     println!("Expiration hours: {}", config.expiration_hours);
     
     let token = create_token(user_id, email.clone(), username.clone(), &config)?;